@@ -0,0 +1,107 @@
+/// Generates the registry of solutions compiled into the runner.
+/// Every `src/bin/<year>_<day>.rs` is included as a module and registered for its puzzle, so that `all` and `verify` can run them in-process.
+/// Also generates the example tests of every solution, included by the `solution!` macro.
+/// The year and day of a solution are those of its file name, the same as for the `YEAR` of the `solution!` macro.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // (bin name, year, day), e.g. ("2023_01", "2023", "01").
+    let mut bins: Vec<(String, String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let stem = path.file_stem()?.to_str()?;
+            let (year, day) = stem.split_once('_')?;
+            if year.len() == 4 && day.len() == 2 && stem.chars().all(|c| c.is_ascii_digit() || c == '_') {
+                Some((stem.to_string(), year.to_string(), day.to_string()))
+            } else {
+                None
+            }
+        })
+        .collect();
+    bins.sort();
+
+    let mut lines = vec!["// @generated by build.rs".to_string()];
+
+    for (name, _, _) in &bins {
+        let path = bin_dir.join(format!("{name}.rs"));
+        lines.push("#[allow(dead_code)]".into());
+        lines.push(format!("#[path = {:?}]", path.display().to_string()));
+        lines.push(format!("mod solution_{name};"));
+    }
+
+    let entries: Vec<String> = bins
+        .iter()
+        .map(|(name, year, day)| {
+            format!(
+                "advent_of_code::template::registry::Solution {{ id: advent_of_code::PuzzleId::new(advent_of_code::year!({year}), advent_of_code::day!({})), run: solution_{name}::RUN }}",
+                day.trim_start_matches('0')
+            )
+        })
+        .collect();
+    lines.push(format!(
+        "pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[{}];",
+        entries.join(", ")
+    ));
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solutions.rs"), lines.join("\n") + "\n").unwrap();
+
+    let data_dir = Path::new(&manifest_dir).join("data");
+    let tests_dir = out_dir.join("example_tests");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    // the solutions are modules of the runner as well, whose tests do not check the examples again.
+    fs::write(tests_dir.join("runner.rs"), "// @generated by build.rs, examples are tested by the solution bins\n").unwrap();
+
+    for (name, year, day) in &bins {
+        let examples_dir = data_dir.join(year).join("examples");
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+        fs::write(tests_dir.join(format!("{name}.rs")), example_tests(&examples_dir, year, day)).unwrap();
+    }
+}
+
+/// One test per part of every `data/<year>/examples/NN-<n>.txt` whose `NN-<n>.toml` sidecar has an expected answer.
+/// The tests call the `part_one` / `part_two` wrappers of the `solution!` macro, which parse the example first if needed.
+fn example_tests(examples_dir: &Path, year: &str, day: &str) -> String {
+    let mut lines = vec!["// @generated by build.rs".to_string()];
+    let id = format!("advent_of_code::PuzzleId::new(advent_of_code::year!({year}), super::DAY)");
+
+    // only `NN-<n>.toml` itself is a sidecar of the day, e.g. not `NN-<n>.toml.bak` or `NN-<n>b.toml`.
+    let mut examples: Vec<usize> = fs::read_dir(examples_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix(&format!("{day}-"))?.strip_suffix(".toml")?.parse().ok()
+        })
+        .filter(|n| *n > 0)
+        .collect();
+    examples.sort_unstable();
+
+    for n in examples {
+        let answers = fs::read_to_string(examples_dir.join(format!("{day}-{n}.toml"))).unwrap();
+
+        for (part, key) in [(1, "part_one"), (2, "part_two")] {
+            if answers.lines().any(|line| line.split_once('=').is_some_and(|(k, _)| k.trim() == key)) {
+                lines.push("#[test]".into());
+                lines.push(format!("fn example_{n}_{key}() {{"));
+                lines.push(format!("    advent_of_code::template::examples::check({key}, {id}, {n}, {part});"));
+                lines.push("}".into());
+            }
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
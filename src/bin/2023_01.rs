@@ -13,7 +13,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     input
       .lines()
       .map(|l| {
-        l.chars().filter(|c| c.is_digit(10)).fold((None, None), |(first, last), c| {
+        l.chars().filter(|c| c.is_ascii_digit()).fold((None, None), |(first, last), c| {
           let i = c.to_string().parse::<u32>().unwrap();
           match (first, last) {
             (None, _) => (Some(i), Some(i)),
@@ -28,7 +28,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
   fn digit(input: &str) -> IResult<&str, Option<u32>> {
-    map_res(take_while_m_n(1, 1, |c: char| c.is_digit(10)), |s| str::parse(s).map(Some))(input)
+    map_res(take_while_m_n(1, 1, |c: char| c.is_ascii_digit()), |s| str::parse(s).map(Some))(input)
   }
   fn in_letters(input: &str) -> IResult<&str, Option<u32>> {
     map(
@@ -43,7 +43,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         value(8, tag_no_case("eight")),
         value(9, tag_no_case("nine")),
       )),
-      |v| Some(v),
+      Some,
     )(input)
   }
   fn in_letters_reversed(input: &str) -> IResult<&str, Option<u32>> {
@@ -59,13 +59,14 @@ pub fn part_two(input: &str) -> Option<u32> {
         value(8, tag_no_case("thgie")),
         value(9, tag_no_case("enin")),
       )),
-      |v| Some(v),
+      Some,
     )(input)
   }
 
   fn first(input: &str) -> Option<u32> {
     iterator(input, alt((digit, in_letters, value(None, anychar)))).into_iter().flatten().next()
   }
+  #[allow(clippy::let_and_return)]
   fn last(input: &str) -> Option<u32> {
     let input = input.chars().rev().collect::<String>();
     let input = input.as_str();
@@ -91,29 +92,13 @@ mod tests {
 
   #[test]
   fn test_part_one() {
-    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_one(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(54597));
   }
 
   #[test]
   fn test_part_two() {
-    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_two(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(54504));
   }
-
-  #[test]
-  fn test_part_two2() {
-    let result = part_two(
-      r"
-two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen
-    ",
-    );
-    assert_eq!(result, Some(281));
-  }
 }
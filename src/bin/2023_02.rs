@@ -105,35 +105,15 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
   use super::*;
 
-  const EXAMPLE: &str = r"
-Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-";
-
   #[test]
   fn test_part_one() {
-    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_one(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(2447));
   }
 
-  #[test]
-  fn test_part_one_example() {
-    let result = part_one(EXAMPLE);
-    assert_eq!(result, Some(8));
-  }
-
   #[test]
   fn test_part_two() {
-    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_two(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(56322));
   }
-
-  #[test]
-  fn test_part_two_example() {
-    let result = part_two(EXAMPLE);
-    assert_eq!(result, Some(2286));
-  }
 }
@@ -9,7 +9,7 @@ static DIGITS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
 
 pub fn part_one<'a>(input: &'a str) -> Option<u32> {
   fn char_is_symbol(c: char) -> bool {
-    !(c.is_digit(10) || c == '.' || c == '\n')
+    !(c.is_ascii_digit() || c == '.' || c == '\n')
   }
 
   fn slice_contains_symbol(slice: &str) -> bool {
@@ -104,19 +104,6 @@ pub fn part_two<'a>(input: &'a str) -> Option<u32> {
 mod tests {
   use super::*;
 
-  const EXAMPLE: &str = r"
-467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..
-";
-
   const EXAMPLE2: &str = r"
 123
 .$.
@@ -131,16 +118,10 @@ mod tests {
 
   #[test]
   fn test_part_one() {
-    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_one(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(536202));
   }
 
-  #[test]
-  fn test_part_one_example() {
-    let result = part_one(EXAMPLE);
-    assert_eq!(result, Some(4361));
-  }
-
   #[test]
   fn test_part_one_example2() {
     let result = part_one(EXAMPLE2);
@@ -155,13 +136,7 @@ mod tests {
 
   #[test]
   fn test_part_two() {
-    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_two(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(78272573));
   }
-
-  #[test]
-  fn test_part_two_example() {
-    let result = part_two(EXAMPLE);
-    assert_eq!(result, Some(467835));
-  }
 }
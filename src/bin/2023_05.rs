@@ -180,62 +180,16 @@ pub fn part_two(input: &str) -> Option<U> {
 mod tests {
   use super::*;
 
-  const EXAMPLE: &str = r#"seeds: 79 14 55 13
-
-  seed-to-soil map:
-  50 98 2
-  52 50 48
-  
-  soil-to-fertilizer map:
-  0 15 37
-  37 52 2
-  39 0 15
-  
-  fertilizer-to-water map:
-  49 53 8
-  0 11 42
-  42 0 7
-  57 7 4
-  
-  water-to-light map:
-  88 18 7
-  18 25 70
-  
-  light-to-temperature map:
-  45 77 23
-  81 45 19
-  68 64 13
-  
-  temperature-to-humidity map:
-  0 69 1
-  1 0 69
-  
-  humidity-to-location map:
-  60 56 37
-  56 93 4"#;
-
   #[test]
   fn test_part_one() {
-    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_one(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(175622908));
   }
 
-  #[test]
-  fn test_part_one_example() {
-    let result = part_one(EXAMPLE);
-    assert_eq!(result, Some(35));
-  }
-
   #[test]
   #[ignore = "too long"]
   fn test_part_two() {
-    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_two(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(5200543));
   }
-
-  #[test]
-  fn test_part_two_example() {
-    let result = part_two(EXAMPLE);
-    assert_eq!(result, Some(46));
-  }
 }
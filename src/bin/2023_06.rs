@@ -29,7 +29,7 @@ fn compute(races: Vec<Race>) -> Option<u64> {
     races
       .into_iter()
       .map(|r| (1..r.time).map(|hold_time| r.end_dist(hold_time)).filter(|end_dist| *end_dist > r.dist).count() as u64)
-      .fold(1, |acc, x| acc * x),
+      .product::<u64>(),
   )
 }
 
@@ -49,30 +49,15 @@ impl Race {
 mod tests {
   use super::*;
 
-  const EXAMPLE: &str = r##"Time:      7  15   30
-Distance:  9  40  200"##;
-
   #[test]
   fn test_part_one() {
-    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_one(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(138915));
   }
 
-  #[test]
-  fn test_part_one_example() {
-    let result = part_one(EXAMPLE);
-    assert_eq!(result, Some(288));
-  }
-
   #[test]
   fn test_part_two() {
-    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_two(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
     assert_eq!(result, Some(27340847));
   }
-
-  #[test]
-  fn test_part_two_example() {
-    let result = part_two(EXAMPLE);
-    assert_eq!(result, Some(71503));
-  }
 }
@@ -1,5 +1,7 @@
 use std::{cmp::Ordering, marker::PhantomData};
 
+use anyhow::{bail, Context};
+
 advent_of_code::solution!(7);
 
 use Card::*;
@@ -104,21 +106,22 @@ impl Rule for P1 {
   }
 }
 
-fn get_inner_hand(hand: &str) -> Option<Hand> {
+fn get_inner_hand(hand: &str) -> anyhow::Result<Hand> {
   let mut cards = hand.chars().filter_map(|c| Card::try_from(c).ok());
   let (Some(c1), Some(c2), Some(c3), Some(c4), Some(c5)) = (cards.next(), cards.next(), cards.next(), cards.next(), cards.next()) else {
-    unimplemented!("Invalid hand: {hand}");
+    bail!("invalid hand: {hand}");
   };
-  Some([c1, c2, c3, c4, c5])
+  Ok([c1, c2, c3, c4, c5])
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str) -> anyhow::Result<u64> {
   compute::<P1>(input)
 }
 
 #[derive(Debug)]
 struct P2;
 impl Rule for P2 {
+  #[allow(clippy::just_underscores_and_digits)]
   fn value(card: &Card) -> u64 {
     match card {
       _A => 0,
@@ -183,26 +186,29 @@ impl Rule for P2 {
   }
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> anyhow::Result<u64> {
   compute::<P2>(input)
 }
 
-fn compute<R: Rule>(input: &str) -> Option<u64> {
+fn parse_play<R: Rule>(hand: &str, bid: &str) -> anyhow::Result<Play<R>> {
+  let hand = get_inner_hand(hand)?;
+  let bid = bid.parse().with_context(|| format!("invalid bid: {bid}"))?;
+  Ok(Play::new(hand, bid))
+}
+
+fn compute<R: Rule>(input: &str) -> anyhow::Result<u64> {
   let mut plays: Vec<Play<R>> = input
     .lines()
-    .filter_map(|l| {
+    .enumerate()
+    .filter_map(|(i, l)| {
       let mut parts = l.split_whitespace();
       let (Some(hand), Some(bid)) = (parts.next(), parts.next()) else {
         return None;
       };
 
-      let hand = get_inner_hand(hand)?;
-      let bid = bid.parse().unwrap();
-
-      let play = Play::new(hand, bid);
-      Some(play)
+      Some(parse_play(hand, bid).with_context(|| format!("line {}", i + 1)))
     })
-    .collect();
+    .collect::<anyhow::Result<_>>()?;
 
   plays.sort_by(|a, b| {
     if a.strength != b.strength {
@@ -217,40 +223,28 @@ fn compute<R: Rule>(input: &str) -> Option<u64> {
   });
   // dbg!(&plays);
 
-  Some(plays.iter().enumerate().map(|(i, play)| (i as u64 + 1) * play.bid).sum())
+  Ok(plays.iter().enumerate().map(|(i, play)| (i as u64 + 1) * play.bid).sum())
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const EXAMPLE: &str = r##"32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483"##;
-
   #[test]
   fn test_part_one() {
-    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
-    assert_eq!(result, Some(249390788));
-  }
-
-  #[test]
-  fn test_part_one_example() {
-    let result = part_one(EXAMPLE);
-    assert_eq!(result, Some(6440));
+    let result = part_one(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
+    assert_eq!(result.unwrap(), 249390788);
   }
 
   #[test]
   fn test_part_two() {
-    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
-    assert_eq!(result, Some(248750248));
+    let result = part_two(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR)));
+    assert_eq!(result.unwrap(), 248750248);
   }
 
   #[test]
-  fn test_part_two_example() {
-    let result = part_two(EXAMPLE);
-    assert_eq!(result, Some(5905));
+  fn test_invalid_hand() {
+    let result = part_one("32T3K 765\n32T3 684");
+    assert_eq!(format!("{:#}", result.unwrap_err()), "line 2: invalid hand: 32T3");
   }
 }
@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse: parse_input);
 
 #[derive(Debug)]
-struct Document {
+pub struct Document {
   instructions: Vec<Instr>,
   nodes: HashMap<NodeId, Node>,
 }
@@ -23,24 +23,19 @@ struct Node {
 
 type NodeId = String;
 
-pub fn part_one(input: &str) -> Option<u64> {
-  let document = parse_input(input);
-  Some(get_steps(&document, "AAA".into(), |id| id == "ZZZ"))
+pub fn part_one(document: &Document) -> Option<u64> {
+  Some(get_steps(document, "AAA".into(), |id| id == "ZZZ"))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-  let document = parse_input(input);
-
+pub fn part_two(document: &Document) -> Option<u64> {
   let solutions = document
     .nodes
     .iter()
     .filter(|(id, _)| id.ends_with("A"))
-    .map(|(id, _)| get_steps(&document, id.clone(), |id| id.ends_with("Z")))
+    .map(|(id, _)| get_steps(document, id.clone(), |id| id.ends_with("Z")))
     .collect::<Vec<_>>();
 
-  dbg!(&solutions);
-
-  Some(solutions.into_iter().fold(1, |acc, s| lcm(acc, s)))
+  Some(solutions.into_iter().fold(1, lcm))
 }
 
 fn get_steps(document: &Document, start_node: NodeId, is_arrived: impl Fn(&str) -> bool) -> u64 {
@@ -77,7 +72,6 @@ fn parse_input(input: &str) -> Document {
     .collect();
 
   let nodes = lines
-    .into_iter()
     .filter_map(|line| {
       if !line.contains("=") {
         return None;
@@ -94,7 +88,7 @@ fn parse_input(input: &str) -> Document {
   Document { instructions, nodes }
 }
 
-/// GIVEN BY CHAT GPT
+// GIVEN BY CHAT GPT
 
 fn lcm(a: u64, b: u64) -> u64 {
   (a * b) / gcd(a, b)
@@ -108,54 +102,21 @@ fn gcd(a: u64, b: u64) -> u64 {
   }
 }
 
-/// GIVEN BY CHAT GPT
+// GIVEN BY CHAT GPT
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const EXAMPLE: &str = r#"RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)"#;
-
   #[test]
   fn test_part_one() {
-    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_one(&parse_input(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR))));
     assert_eq!(result, Some(22411));
   }
 
-  #[test]
-  fn test_part_one_example() {
-    let result = part_one(EXAMPLE);
-    assert_eq!(result, Some(2));
-  }
-
-  const EXAMPLE2: &str = r#"LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)"#;
-
   #[test]
   fn test_part_two() {
-    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+    let result = part_two(&parse_input(&advent_of_code::template::read_puzzle_file("inputs", advent_of_code::template::puzzle_id(DAY, YEAR))));
     assert_eq!(result, Some(11188774513823));
   }
-
-  #[test]
-  fn test_part_two_example() {
-    let result = part_two(EXAMPLE2);
-    assert_eq!(result, Some(6));
-  }
 }
@@ -80,6 +80,100 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// A year of advent (i.e. an integer from 2015, the first edition, onwards).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub const fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year from 2015 onwards")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as the year and the two digit day.
+///
+/// ```
+/// # use advent_of_code::{PuzzleId, Day, Year};
+/// let id = PuzzleId::new(Year::new(2022).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(id.to_string(), "2022/08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -114,6 +208,127 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A selection of days, as given on the command line.
+///
+/// Items are separated by commas, each item is either:
+/// - a day: `4`,
+/// - a range, excluding its end: `3..7`, or including it: `3..=7`. Either bound can be omitted: `20..`,
+/// - `latest`: the latest day, which the command defines, e.g. the last day with a solution,
+/// - `unsolved`: the days with a solution that do not have an accepted answer for both parts yet.
+///
+/// ```
+/// # use advent_of_code::{Day, DaySelection};
+/// let selection: DaySelection = "1,3..5,latest".parse().unwrap();
+/// let days = selection.resolve(Day::new(8), &[], |_| true);
+/// assert_eq!(days.iter().map(|d| d.into_inner()).collect::<Vec<_>>(), vec![1, 3, 4, 8])
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(Vec<DaySelector>);
+
+/// An item of a [`DaySelection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaySelector {
+    /// The days from the first to the second, included.
+    Range(Day, Day),
+    Latest,
+    Unsolved,
+}
+
+impl DaySelection {
+    /// Selects every day of advent.
+    pub fn all() -> Self {
+        Self(vec![DaySelector::Range(Day(1), Day(25))])
+    }
+
+    /// Returns the selected days in order. `latest` is the day selected by `latest`, `scaffolded` are the days
+    /// with a solution, `is_solved` tells whether a day has an accepted answer for both parts.
+    pub fn resolve(&self, latest: Option<Day>, scaffolded: &[Day], is_solved: impl Fn(Day) -> bool) -> Vec<Day> {
+        let mut days: Vec<Day> = vec![];
+
+        for selector in &self.0 {
+            match *selector {
+                DaySelector::Range(first, last) => days.extend((first.0..=last.0).map(Day)),
+                DaySelector::Latest => days.extend(latest),
+                DaySelector::Unsolved => days.extend(scaffolded.iter().filter(|d| !is_solved(**d))),
+            }
+        }
+
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    /// Whether `latest` is part of the selection.
+    pub fn contains_latest(&self) -> bool {
+        self.0.contains(&DaySelector::Latest)
+    }
+}
+
+impl From<Day> for DaySelection {
+    fn from(day: Day) -> Self {
+        Self(vec![DaySelector::Range(day, day)])
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DaySelectionFromStrError(s.to_string());
+
+        let parse_bound = |bound: &str, default: u8| -> Result<u8, Self::Err> {
+            if bound.is_empty() {
+                return Ok(default);
+            }
+            bound.parse().map_err(|_| error())
+        };
+
+        let selectors = s
+            .split(',')
+            .map(str::trim)
+            .map(|item| match item {
+                "latest" => Ok(DaySelector::Latest),
+                "unsolved" => Ok(DaySelector::Unsolved),
+                _ => {
+                    let (first, last) = if let Some((first, last)) = item.split_once("..=") {
+                        (parse_bound(first, 1)?, parse_bound(last, 25)?)
+                    } else if let Some((first, last)) = item.split_once("..") {
+                        (parse_bound(first, 1)?, parse_bound(last, 26)?.checked_sub(1).ok_or_else(error)?)
+                    } else {
+                        let day = item.parse().map_err(|_| error())?;
+                        (day, day)
+                    };
+
+                    match (Day::new(first), Day::new(last)) {
+                        (Some(first), Some(last)) if first <= last => Ok(DaySelector::Range(first, last)),
+                        _ => Err(error()),
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self(selectors))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError(String);
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days between 1 and 25, ranges like `3..7`, `latest` or `unsolved`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -130,11 +345,61 @@ macro_rules! day {
     }};
 }
 
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!("invalid year `", $year, "`, expecting a year from 2015 onwards"),
+        );
+        $crate::Year::__new_unchecked($year)
+    }};
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySelection, Year};
+
+    fn select(s: &str) -> Vec<u8> {
+        let selection: DaySelection = s.parse().unwrap();
+        selection
+            .resolve(Some(Day(9)), &[Day(2), Day(5), Day(9)], |day| day == Day(5))
+            .into_iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn parses_day_selections() {
+        assert_eq!(select("4"), vec![4]);
+        assert_eq!(select("3..7"), vec![3, 4, 5, 6]);
+        assert_eq!(select("3..=7"), vec![3, 4, 5, 6, 7]);
+        assert_eq!(select("8,1, 4"), vec![1, 4, 8]);
+        assert_eq!(select("23.."), vec![23, 24, 25]);
+        assert_eq!(select("..=2,2"), vec![1, 2]);
+        assert_eq!(select("latest"), vec![9]);
+        assert_eq!(select("unsolved"), vec![2, 9]);
+    }
+
+    #[test]
+    fn errors_on_invalid_day_selections() {
+        assert_eq!("0".parse::<DaySelection>().is_err(), true);
+        assert_eq!("7..3".parse::<DaySelection>().is_err(), true);
+        assert_eq!("3..3".parse::<DaySelection>().is_err(), true);
+        assert_eq!("1..=26".parse::<DaySelection>().is_err(), true);
+        assert_eq!("first".parse::<DaySelection>().is_err(), true);
+        assert_eq!("1,".parse::<DaySelection>().is_err(), true);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2022".parse::<Year>().unwrap(), Year(2022));
+        assert_eq!("2014".parse::<Year>().is_err(), true);
+        assert_eq!("22".parse::<Year>().is_err(), true);
+    }
 
     #[test]
     fn all_days_iterator() {
@@ -1,31 +1,122 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, bench_compare, bench_diff, download, examples, progress, read, scaffold, solve, verify};
+use advent_of_code::template::{select_days, select_unlocked_days};
+use advent_of_code::PuzzleId;
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
+    use std::time::Duration;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::report::{ReportFormat, ReportOptions};
+    use advent_of_code::template::{get_year, InputSource};
+    use advent_of_code::{Day, DaySelection, Year};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            year: Year,
+            days: DaySelection,
         },
         Read {
+            year: Year,
             day: Day,
         },
-        Scaffold {
+        Examples {
+            year: Year,
             day: Day,
         },
+        Scaffold {
+            year: Year,
+            days: DaySelection,
+        },
         Solve {
-            day: Day,
+            year: Year,
+            days: DaySelection,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            report: Option<ReportOptions>,
+            watch: bool,
+            timeout: Option<Duration>,
+            input: InputSource,
         },
         All {
+            year: Year,
             release: bool,
             time: bool,
+            isolated: bool,
+            timeout: Option<Duration>,
+            jobs: usize,
+            parallel_timing: bool,
+            report: Option<ReportOptions>,
+            days: DaySelection,
         },
+        Verify {
+            year: Year,
+            day: Option<Day>,
+        },
+        BenchDiff {
+            year: Year,
+            baseline: Option<String>,
+        },
+        Progress {
+            year: Year,
+        },
+        BenchCompare {
+            year: Year,
+            before: String,
+            after: String,
+            days: DaySelection,
+        },
+    }
+
+    /// The year given with `--year`, defaults to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        year.or_else(get_year)
+            .ok_or_else(|| "no year selected, pass --year or set the AOC_YEAR environment variable".into())
+    }
+
+    fn parse_report(args: &mut pico_args::Arguments) -> Result<Option<ReportOptions>, pico_args::Error> {
+        let format: Option<ReportFormat> = args.opt_value_from_str("--report")?;
+        let path = args.opt_value_from_str("--out")?;
+
+        Ok(format.map(|format| ReportOptions {
+            format,
+            path: path.unwrap_or_else(|| format.default_path()),
+        }))
+    }
+
+    /// The timeout given with `--timeout <secs>`.
+    fn parse_timeout(args: &mut pico_args::Arguments) -> Result<Option<Duration>, pico_args::Error> {
+        args.opt_value_from_fn("--timeout", |s| s.parse().map(Duration::from_secs))
+    }
+
+    /// The number of days to run at once given with `--jobs <n>`, defaults to one.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--jobs")? {
+            Some(0) => Err("--jobs must be at least 1".into()),
+            jobs => Ok(jobs.unwrap_or(1)),
+        }
+    }
+
+    /// The input given with `--input <path>`, `--input -` or `--example [n]`, defaults to the puzzle input.
+    /// Parsed last, as the example number is an optional free argument.
+    fn parse_input(args: &mut pico_args::Arguments) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let path: Option<String> = args.opt_value_from_str("--input")?;
+        let example = if args.contains("--example") {
+            Some(args.opt_free_from_str::<usize>()?.unwrap_or(1))
+        } else {
+            None
+        };
+
+        match (path, example) {
+            (Some(_), Some(_)) => Err("--input and --example can not be combined".into()),
+            (Some(path), None) if path == "-" => Ok(InputSource::Stdin),
+            (Some(path), None) => Ok(InputSource::File(path.into())),
+            (None, Some(0)) => Err("examples are numbered from 1".into()),
+            (None, Some(n)) => Ok(InputSource::Example(n)),
+            (None, None) => Ok(InputSource::Puzzle),
+        }
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -33,23 +124,59 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                isolated: args.contains("--isolated"),
+                timeout: parse_timeout(&mut args)?,
+                jobs: parse_jobs(&mut args)?,
+                parallel_timing: args.contains("--parallel-timing"),
+                report: parse_report(&mut args)?,
+                days: args.opt_free_from_str()?.unwrap_or_else(DaySelection::all),
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                year: parse_year(&mut args)?,
+                days: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year: parse_year(&mut args)?,
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("examples") => AppArguments::Examples {
+                year: parse_year(&mut args)?,
                 day: args.free_from_str()?,
             },
+            Some("scaffold") => AppArguments::Scaffold {
+                year: parse_year(&mut args)?,
+                days: args.free_from_str()?,
+            },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                year: parse_year(&mut args)?,
+                days: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                report: parse_report(&mut args)?,
+                watch: args.contains("--watch"),
+                timeout: parse_timeout(&mut args)?,
+                input: parse_input(&mut args)?,
+            },
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("bench-diff") => AppArguments::BenchDiff {
+                year: parse_year(&mut args)?,
+                baseline: args.opt_value_from_str("--baseline")?,
+            },
+            Some("progress") => AppArguments::Progress {
+                year: parse_year(&mut args)?,
+            },
+            Some("bench-compare") => AppArguments::BenchCompare {
+                year: parse_year(&mut args)?,
+                before: args.free_from_str()?,
+                after: args.free_from_str()?,
+                days: args.opt_free_from_str()?.unwrap_or_else(DaySelection::all),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -77,16 +204,46 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                time,
+                isolated,
+                timeout,
+                jobs,
+                parallel_timing,
+                report,
+                days,
+            } => all::handle(
+                year,
+                &select_days(&days, year),
+                release,
+                time,
+                isolated,
+                timeout,
+                jobs,
+                parallel_timing,
+                report,
+            ),
+            AppArguments::Download { year, days } => download::handle(year, &select_unlocked_days(&days, year)),
+            AppArguments::Read { year, day } => read::handle(PuzzleId::new(year, day)),
+            AppArguments::Examples { year, day } => examples::handle(PuzzleId::new(year, day)),
+            AppArguments::Scaffold { year, days } => scaffold::handle(year, &select_unlocked_days(&days, year)),
             AppArguments::Solve {
-                day,
+                year,
+                days,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                report,
+                watch,
+                timeout,
+                input,
+            } => solve::handle(year, &select_days(&days, year), release, time, submit, report, watch, &input, timeout),
+            AppArguments::Verify { year, day } => verify::handle(year, day),
+            AppArguments::BenchDiff { year, baseline } => bench_diff::handle(year, baseline.as_deref()),
+            AppArguments::Progress { year } => progress::handle(year),
+            AppArguments::BenchCompare { year, before, after, days } => bench_compare::handle(year, &select_days(&days, year), &before, &after),
         },
     };
 }
@@ -0,0 +1,8 @@
+/// Runs the solutions in-process for `all` and `verify`, see [`registry::serve`](advent_of_code::template::registry::serve).
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+fn main() {
+    advent_of_code::template::registry::serve(solutions::SOLUTIONS);
+}
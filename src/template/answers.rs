@@ -0,0 +1,144 @@
+/// Module that stores the accepted answer of each part in `data/<year>/answers/NN.toml`.
+/// This is the source of truth that `verify` and the reports check solutions against.
+use std::{fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::PuzzleId;
+
+use super::get_data_dir;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answers: {e}"),
+            Error::IO(e) => write!(f, "could not access answers file: {e}"),
+        }
+    }
+}
+
+/// The accepted answers of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    /// Returns the accepted answer of a part, if known.
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set_part(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_one = Some(answer.into()),
+            2 => self.part_two = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.part_one.is_none() && self.part_two.is_none()
+    }
+
+    fn from_toml(s: &str) -> Result<Self, Error> {
+        toml::from_str(s).map_err(|e| Error::Parser(e.to_string()))
+    }
+
+    fn to_toml(&self) -> Result<String, Error> {
+        toml::to_string(self).map_err(|e| Error::Parser(e.to_string()))
+    }
+}
+
+#[must_use]
+pub fn get_path_for_answers(id: PuzzleId) -> String {
+    format!("{}/{}.toml", get_data_dir(id.year, "answers"), id.day)
+}
+
+/// Reads the accepted answers of a day, a missing file means that no answer is known yet.
+pub fn load(id: PuzzleId) -> Result<Answers, Error> {
+    load_from(&get_path_for_answers(id))
+}
+
+pub fn save(id: PuzzleId, answers: &Answers) -> Result<(), Error> {
+    save_to(&get_path_for_answers(id), answers)
+}
+
+/// Reads answers from any file in the answers format, e.g. the expected answers of an example.
+pub fn load_from(path: &str) -> Result<Answers, Error> {
+    if !Path::new(path).exists() {
+        return Ok(Answers::default());
+    }
+    Answers::from_toml(&fs::read_to_string(path)?)
+}
+
+pub fn save_to(path: &str, answers: &Answers) -> Result<(), Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, answers.to_toml()?)?;
+    Ok(())
+}
+
+/// Stores the accepted answer of a part, keeping the other part as is.
+pub fn record(id: PuzzleId, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = load(id)?;
+    answers.set_part(part, answer);
+    save(id, &answers)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn parses_answers() {
+        let answers = Answers::from_toml("part_one = \"54597\"\npart_two = \"54504\"\n").unwrap();
+        assert_eq!(answers.part(1), Some("54597"));
+        assert_eq!(answers.part(2), Some("54504"));
+        assert_eq!(answers.part(3), None);
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers = Answers::from_toml("part_one = \"1\"\n").unwrap();
+        assert_eq!(answers.part(1), Some("1"));
+        assert_eq!(answers.part(2), None);
+        assert_eq!(Answers::from_toml("").unwrap().is_empty(), true);
+    }
+
+    #[test]
+    fn errors_on_invalid_answers() {
+        assert_eq!(Answers::from_toml("part_one = 1").is_err(), true);
+    }
+
+    #[test]
+    fn serializes_answers() {
+        let mut answers = Answers::default();
+        answers.set_part(2, "multi\nline");
+        let s = answers.to_toml().unwrap();
+        assert_eq!(s.contains("part_one"), false);
+        assert_eq!(Answers::from_toml(&s).unwrap(), answers);
+    }
+}
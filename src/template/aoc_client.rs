@@ -0,0 +1,370 @@
+/// Native client for the Advent of Code website.
+/// Downloads puzzles and inputs and submits answers over HTTP, authenticated with the session cookie
+/// read from the `AOC_SESSION` environment variable or the `~/.adventofcode.session` file.
+/// The `AOC_BASE_URL` environment variable points the client to another server, e.g. a local mock.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{commands::all::get_path_for_input, examples::get_path_for_puzzle};
+use crate::{Day, PuzzleId, Year};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/jrouaix/aoc2023";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or the session file.
+    SessionNotFound,
+    /// The session cookie was refused, it probably expired.
+    SessionExpired,
+    /// The puzzle of that day is not unlocked yet.
+    PuzzleNotUnlocked,
+    /// Too many requests were sent, `retry_after` is in seconds.
+    RateLimited { retry_after: Option<u64> },
+    /// The website answered with an unexpected status.
+    BadStatus(u16),
+    /// The website could not be reached.
+    Transport(String),
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => {
+                write!(f, "no session cookie found, set AOC_SESSION or create a ~/.adventofcode.session file.")
+            }
+            AocClientError::SessionExpired => write!(f, "the session cookie was refused, it has probably expired."),
+            AocClientError::PuzzleNotUnlocked => write!(f, "this puzzle is not unlocked yet."),
+            AocClientError::RateLimited { retry_after: Some(secs) } => write!(f, "rate limited, retry in {secs}s."),
+            AocClientError::RateLimited { retry_after: None } => write!(f, "rate limited, retry later."),
+            AocClientError::BadStatus(status) => write!(f, "unexpected response status {status}."),
+            AocClientError::Transport(e) => write!(f, "could not reach the website: {e}"),
+            AocClientError::IoError(e) => write!(f, "could not write output files to file system: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                let retry_after = response.header("Retry-After").and_then(|x| x.trim().parse().ok());
+                let body = response.into_string().unwrap_or_default();
+                match status {
+                    401 | 403 => AocClientError::SessionExpired,
+                    400 | 500 if body.contains("log in") => AocClientError::SessionExpired,
+                    404 => AocClientError::PuzzleNotUnlocked,
+                    429 => AocClientError::RateLimited { retry_after },
+                    status => AocClientError::BadStatus(status),
+                }
+            }
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Creates a client for the puzzles of a year, using the base url and session cookie of the environment.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&get_base_url(), &session, year))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self.agent.get(url).set("Cookie", &format!("session={}", self.session)).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the description of a puzzle, converted to markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(html_to_markdown(&extract_description(&html)))
+    }
+
+    /// Fetches the puzzle input of the logged-in user.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Submits an answer, returns the text of the response.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        let html = response.into_string()?;
+        Ok(html_to_markdown(&extract_articles(&html).join("\n")).trim().to_string())
+    }
+}
+
+pub fn read(id: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(id.year)?;
+    let puzzle = client.puzzle(id.day)?;
+    let puzzle_path = get_path_for_puzzle(id);
+    create_parent_dir(&puzzle_path)?;
+    fs::write(puzzle_path, &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+pub fn download(id: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(id.year)?;
+    let input_path = get_path_for_input(id);
+    let puzzle_path = get_path_for_puzzle(id);
+
+    let input = client.input(id.day)?;
+    let puzzle = client.puzzle(id.day)?;
+
+    create_parent_dir(&input_path)?;
+    create_parent_dir(&puzzle_path)?;
+    fs::write(&input_path, input)?;
+    fs::write(&puzzle_path, puzzle)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Submits an answer and prints the response, which is also returned.
+pub fn submit(id: PuzzleId, part: u8, result: &str) -> Result<String, AocClientError> {
+    let response = AocClient::from_env(id.year)?.submit(id.day, part, result)?;
+    println!("{response}");
+    Ok(response)
+}
+
+fn create_parent_dir(path: &str) -> Result<(), io::Error> {
+    match Path::new(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+fn get_base_url() -> String {
+    match env::var("AOC_BASE_URL") {
+        Ok(x) if !x.trim().is_empty() => x.trim().to_string(),
+        _ => DEFAULT_BASE_URL.to_string(),
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session.trim().to_string());
+        }
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(".adventofcode.session")).ok()?;
+    Some(session.trim().to_string()).filter(|x| !x.is_empty())
+}
+
+/// Returns the `<article>` elements of a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        let end = start + end + "</article>".len();
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+/// Returns the description of a puzzle page: its articles and the answers given so far.
+fn extract_description(html: &str) -> String {
+    let mut parts = vec![];
+    let mut rest = html;
+
+    loop {
+        let article = rest.find("<article");
+        let answer = rest.find("<p>Your puzzle answer was");
+
+        let (start, end_tag) = match (article, answer) {
+            (Some(a), Some(b)) if b < a => (b, "</p>"),
+            (Some(a), _) => (a, "</article>"),
+            (None, Some(b)) => (b, "</p>"),
+            (None, None) => break,
+        };
+
+        let Some(end) = rest[start..].find(end_tag) else {
+            break;
+        };
+        let end = start + end + end_tag.len();
+        parts.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    parts.join("\n")
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+/// Converts the HTML of a puzzle description to markdown, in the flavor written by aoc-cli.
+#[must_use]
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_end) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_end];
+        rest = &rest[tag_start + tag_end + 1..];
+
+        let name = tag.split_whitespace().next().unwrap_or_default().to_ascii_lowercase();
+
+        match name.as_str() {
+            "h2" => out.push('\\'),
+            "/h2" => out.push_str("\n----------\n\n"),
+            "/p" => out.push_str("\n\n"),
+            "pre" => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                out.push_str("\n```\n\n");
+            }
+            "em" | "/em" if !in_pre => out.push('*'),
+            "code" | "/code" if !in_pre => out.push('`'),
+            "a" => {
+                links.push(get_attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            "/a" => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            "li" => out.push_str("* "),
+            "/li" => out.push('\n'),
+            "/ul" => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    // the whitespace between elements is not part of the description.
+    if !in_pre && text.trim().is_empty() && text.contains('\n') {
+        return;
+    }
+
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+    } else {
+        out.push_str(&text.replace('*', "\\*"));
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_description, html_to_markdown};
+
+    #[test]
+    fn converts_descriptions() {
+        let html = [
+            r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>"#,
+            r#"<p>Check all <em>fifty stars</em> and a <a href="/2015/day/1">weather machine</a>.</p>"#,
+            r#"<pre><code>1abc2"#,
+            r#"a<em>1</em>b&lt;2"#,
+            r#"</code></pre>"#,
+            r#"<p>This produces <code><em>142</em></code> <em class="star">*</em></p>"#,
+            r#"</article>"#,
+        ]
+        .join("\n");
+
+        let expected = [
+            r"\--- Day 1: Trebuchet?! ---",
+            r"----------",
+            r"",
+            r"Check all *fifty stars* and a [weather machine](/2015/day/1).",
+            r"",
+            r"```",
+            r"1abc2",
+            r"a1b<2",
+            r"",
+            r"```",
+            r"",
+            r"This produces `*142*` *\**",
+        ]
+        .join("\n")
+            + "\n";
+
+        assert_eq!(html_to_markdown(&html), expected);
+    }
+
+    #[test]
+    fn extracts_descriptions() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1 ---</h2></article><p>Your puzzle answer was <code>1</code>.</p><form></form></main>"#;
+        assert_eq!(
+            extract_description(html),
+            "<article class=\"day-desc\"><h2>--- Day 1 ---</h2></article>\n<p>Your puzzle answer was <code>1</code>.</p>"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+/// Module that keeps the history of the benchmarks in `data/<year>/benchmarks.jsonl`, one run per line.
+/// Every `cargo time` run is appended along with the date, git commit and rustc version it ran with,
+/// so that `bench-diff` can tell whether a change made a part slower.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::readme_benchmarks::Timings;
+use crate::template::runner::{BenchStats, PARSE_STEP};
+use crate::Year;
+
+use super::get_data_dir;
+
+/// The value of Welch's t-statistic above which a change is taken as significant, about 99% confidence for the sample counts of a bench.
+const T_CRITICAL: f64 = 2.6;
+
+/// Changes of the median smaller than this fraction of the baseline are noise, however significant.
+const MIN_CHANGE: f64 = 0.1;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark history: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark history: {e}"),
+        }
+    }
+}
+
+/// A `cargo time` run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// UTC, e.g. `2023-12-07T06:12:45Z`.
+    pub date: String,
+    /// The short hash of `HEAD`, suffixed with `-dirty` if there were uncommitted changes.
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub parts: Vec<PartTiming>,
+}
+
+/// The timing of a part of a day, part [`PARSE_STEP`] being the parse step.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PartTiming {
+    pub day: u8,
+    pub part: u8,
+    pub samples: u64,
+    pub median_nanos: u64,
+    pub mean_nanos: u64,
+    pub std_dev_nanos: u64,
+}
+
+impl PartTiming {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(day: u8, part: u8, stats: &BenchStats) -> Self {
+        Self {
+            day,
+            part,
+            samples: stats.samples as u64,
+            median_nanos: stats.median.as_nanos() as u64,
+            mean_nanos: stats.mean.as_nanos() as u64,
+            std_dev_nanos: stats.std_dev.as_nanos() as u64,
+        }
+    }
+}
+
+impl Run {
+    /// A run of the benchmarks of `timings`, made now from the current checkout.
+    #[must_use]
+    pub fn new(timings: &[Timings]) -> Self {
+        let parts = timings
+            .iter()
+            .flat_map(|t| {
+                let day = t.day.into_inner();
+                [(PARSE_STEP, t.parse_stats), (1, t.part_1_stats), (2, t.part_2_stats)]
+                    .into_iter()
+                    .filter_map(move |(part, stats)| stats.map(|s| PartTiming::new(day, part, &s)))
+            })
+            .collect();
+
+        Self {
+            date: format_date(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()),
+            commit: git_commit(),
+            rustc: command_output("rustc", &["--version"]),
+            parts,
+        }
+    }
+
+    fn part(&self, day: u8, part: u8) -> Option<&PartTiming> {
+        self.parts.iter().find(|p| p.day == day && p.part == part)
+    }
+
+    /// Describes the run, e.g. `2023-12-07T06:12:45Z (1a2b3c4)`.
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.date, self.commit.as_deref().unwrap_or("unknown commit"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+/// How the timing of a part changed between two runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub latest: Duration,
+    /// The change of the median, as a fraction of the baseline.
+    pub change: f64,
+    pub verdict: Verdict,
+}
+
+/// Compares the parts that were timed in both runs. A part regressed or improved if its median changed by more than
+/// [`MIN_CHANGE`], and Welch's t-test says that the change of its mean is not down to the spread of the samples.
+#[must_use]
+pub fn compare(baseline: &Run, latest: &Run) -> Vec<Comparison> {
+    latest
+        .parts
+        .iter()
+        .filter_map(|new| {
+            let old = baseline.part(new.day, new.part)?;
+            let change = relative_change(old.median_nanos, new.median_nanos);
+            let t = welch(old, new);
+
+            let verdict = if change.abs() < MIN_CHANGE || t.abs() < T_CRITICAL || t.signum() != change.signum() {
+                Verdict::Unchanged
+            } else if change > 0.0 {
+                Verdict::Regressed
+            } else {
+                Verdict::Improved
+            };
+
+            Some(Comparison {
+                day: new.day,
+                part: new.part,
+                baseline: Duration::from_nanos(old.median_nanos),
+                latest: Duration::from_nanos(new.median_nanos),
+                change,
+                verdict,
+            })
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn relative_change(old: u64, new: u64) -> f64 {
+    if old == 0 {
+        return 0.0;
+    }
+    (new as f64 - old as f64) / old as f64
+}
+
+/// Welch's t-statistic of the means of two timings.
+#[allow(clippy::cast_precision_loss)]
+fn welch(old: &PartTiming, new: &PartTiming) -> f64 {
+    let (old_mean, new_mean) = (old.mean_nanos as f64, new.mean_nanos as f64);
+
+    if old.samples < 2 || new.samples < 2 {
+        return 0.0;
+    }
+
+    let variance = |t: &PartTiming| (t.std_dev_nanos as f64).powi(2) / t.samples as f64;
+    let error = (variance(old) + variance(new)).sqrt();
+
+    if error > 0.0 {
+        (new_mean - old_mean) / error
+    } else if new_mean == old_mean {
+        0.0
+    } else {
+        // without any spread, every change is significant.
+        f64::INFINITY.copysign(new_mean - old_mean)
+    }
+}
+
+#[must_use]
+pub fn get_path_for_history(year: Year) -> String {
+    get_data_dir(year, "benchmarks.jsonl")
+}
+
+/// Appends a run to the history of a year.
+pub fn append(year: Year, run: &Run) -> Result<(), Error> {
+    fs::create_dir_all(get_data_dir(year, ""))?;
+    let line = serde_json::to_string(run).map_err(|e| Error::Parser(e.to_string()))?;
+    let mut file = OpenOptions::new().create(true).append(true).open(get_path_for_history(year))?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Reads the runs of a year, oldest first. A missing file means no runs.
+pub fn load(year: Year) -> Result<Vec<Run>, Error> {
+    let path = get_path_for_history(year);
+    if !Path::new(&path).exists() {
+        return Ok(vec![]);
+    }
+    parse(&fs::read_to_string(path)?)
+}
+
+fn parse(s: &str) -> Result<Vec<Run>, Error> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| Error::Parser(format!("line {}: {e}", i + 1))))
+        .collect()
+}
+
+fn git_commit() -> Option<String> {
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = command_output("git", &["status", "--porcelain", "--untracked-files=no"]).is_some();
+    Some(if is_dirty { format!("{commit}-dirty") } else { commit })
+}
+
+/// The trimmed stdout of a successful command, [`None`] if it failed or printed nothing.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok().filter(|o| o.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+/// Formats the time since the unix epoch as an ISO 8601 UTC date.
+fn format_date(since_epoch: Duration) -> String {
+    let secs = since_epoch.as_secs();
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    // converts the days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, format_date, parse, PartTiming, Run, Verdict};
+    use std::time::Duration;
+
+    fn run(parts: &[(u8, u8, u64, u64)]) -> Run {
+        Run {
+            date: "2023-12-07T06:12:45Z".into(),
+            commit: Some("1a2b3c4".into()),
+            rustc: None,
+            parts: parts
+                .iter()
+                .map(|&(day, part, mean_nanos, std_dev_nanos)| PartTiming {
+                    day,
+                    part,
+                    samples: 100,
+                    median_nanos: mean_nanos,
+                    mean_nanos,
+                    std_dev_nanos,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(Duration::ZERO), "1970-01-01T00:00:00Z");
+        assert_eq!(format_date(Duration::from_secs(1_701_929_565)), "2023-12-07T06:12:45Z");
+        assert_eq!(format_date(Duration::from_secs(1_709_210_096)), "2024-02-29T12:34:56Z");
+    }
+
+    #[test]
+    fn compares_runs() {
+        let baseline = run(&[(7, 1, 1_000_000, 50_000), (7, 2, 1_000_000, 50_000), (8, 1, 1_000_000, 50_000), (9, 1, 1_000_000, 2_000_000)]);
+        let latest = run(&[(7, 1, 1_200_000, 50_000), (7, 2, 800_000, 50_000), (8, 1, 1_010_000, 50_000), (9, 1, 1_200_000, 2_000_000), (10, 1, 5, 1)]);
+
+        let verdicts: Vec<_> = compare(&baseline, &latest).iter().map(|c| (c.day, c.part, c.verdict)).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                (7, 1, Verdict::Regressed),
+                (7, 2, Verdict::Improved),
+                // too small a change.
+                (8, 1, Verdict::Unchanged),
+                // too much spread.
+                (9, 1, Verdict::Unchanged),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_history() {
+        let line = serde_json::to_string(&run(&[(1, 0, 10, 1), (1, 1, 20, 2)])).unwrap();
+        let runs = parse(&format!("{line}\n\n{line}\n")).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0], run(&[(1, 0, 10, 1), (1, 1, 20, 2)]));
+        assert_eq!(parse("{}\n").is_err(), true);
+    }
+}
@@ -0,0 +1,201 @@
+/// Module that draws the readme benchmarks as a bar chart in `.assets/benchmarks.svg`.
+/// Each part gets a bar on a log scale, so that days taking microseconds and days taking seconds fit the same chart.
+use std::{fs, io, path::Path, time::Duration};
+
+use crate::template::readme_benchmarks::Timings;
+use crate::template::runner::BenchStats;
+
+pub static CHART_PATH: &str = ".assets/benchmarks.svg";
+
+const WIDTH: f64 = 800.0;
+const LABEL_WIDTH: f64 = 64.0;
+/// Room for the value printed behind the longest bar.
+const VALUE_WIDTH: f64 = 72.0;
+const TOP: f64 = 56.0;
+const BAR_HEIGHT: f64 = 12.0;
+const BAR_GAP: f64 = 2.0;
+const DAY_GAP: f64 = 10.0;
+
+const STEPS: [(&str, &str); 3] = [("Parse", "#7f8c8d"), ("Part 1", "#c0392b"), ("Part 2", "#27ae60")];
+
+/// A bar of the chart, parts without a timing (e.g. timed out ones) only show their text.
+struct Bar {
+    color: &'static str,
+    nanos: Option<f64>,
+    text: String,
+}
+
+/// The bars of each day that has a timing.
+fn collect_bars(timings: &[Timings]) -> Vec<(String, Vec<Bar>)> {
+    timings
+        .iter()
+        .map(|t| {
+            let steps = [(&t.parse, t.parse_stats), (&t.part_1, t.part_1_stats), (&t.part_2, t.part_2_stats)];
+            let bars = steps
+                .into_iter()
+                .zip(STEPS)
+                .filter(|((text, stats), _)| text.is_some() || stats.is_some())
+                .map(|((text, stats), (_, color))| Bar {
+                    color,
+                    nanos: stats.map(|s: BenchStats| s.median.as_secs_f64() * 1e9),
+                    text: stats.map_or_else(|| text.clone().unwrap_or_default(), |s| format!("{:.1?}", s.median)),
+                })
+                .collect::<Vec<_>>();
+            (format!("Day {}", t.day), bars)
+        })
+        .filter(|(_, bars)| !bars.is_empty())
+        .collect()
+}
+
+/// The decades that the axis spans, in nanoseconds, e.g. `(3, 9)` for 1µs to 1s.
+fn axis_range(bars: &[(String, Vec<Bar>)]) -> (i32, i32) {
+    let nanos = bars.iter().flat_map(|(_, bars)| bars.iter().filter_map(|b| b.nanos)).filter(|&n| n > 0.0);
+    let (min, max) = nanos.fold((f64::MAX, f64::MIN), |(min, max), n| (min.min(n), max.max(n)));
+    if min > max {
+        return (0, 3);
+    }
+
+    // the axis starts a decade below the fastest part, so that its bar does not have zero width.
+    #[allow(clippy::cast_possible_truncation)]
+    let (lo, hi) = (min.log10().ceil() as i32 - 1, max.log10().ceil() as i32);
+    (lo, hi.max(lo + 1))
+}
+
+/// The width of a bar of `nanos` on a log scale from `10^lo` to `10^hi`.
+fn scale(nanos: f64, (lo, hi): (i32, i32), width: f64) -> f64 {
+    let position = (nanos.max(1.0).log10() - f64::from(lo)) / f64::from(hi - lo);
+    position.clamp(0.0, 1.0) * width
+}
+
+/// Renders the chart, [`None`] if there is nothing to draw.
+#[must_use]
+pub fn render(timings: &[Timings]) -> Option<String> {
+    let days = collect_bars(timings);
+    if days.is_empty() {
+        return None;
+    }
+
+    let range = axis_range(&days);
+    let plot_width = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let rows: usize = days.iter().map(|(_, bars)| bars.len()).sum();
+    #[allow(clippy::cast_precision_loss)]
+    let height = TOP + rows as f64 * (BAR_HEIGHT + BAR_GAP) + days.len() as f64 * DAY_GAP;
+
+    let mut svg = vec![
+        format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="11">"#),
+        format!(r##"<rect width="{WIDTH}" height="{height}" fill="#ffffff"/>"##),
+    ];
+
+    // legend of the steps that have bars.
+    let mut x = LABEL_WIDTH;
+    for (label, color) in STEPS.into_iter().filter(|(_, color)| days.iter().any(|(_, bars)| bars.iter().any(|b| b.color == *color))) {
+        svg.push(format!(r#"<rect x="{x}" y="8" width="10" height="10" fill="{color}"/>"#));
+        svg.push(format!(r#"<text x="{}" y="17">{label}</text>"#, x + 14.0));
+        x += 70.0;
+    }
+
+    // a grid line for each decade.
+    for decade in range.0..=range.1 {
+        let x = LABEL_WIDTH + scale(10_f64.powi(decade), range, plot_width);
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        let label = format!("{:?}", Duration::from_nanos(10_u64.pow(decade as u32)));
+        svg.push(format!(r##"<line x1="{x:.1}" y1="{}" x2="{x:.1}" y2="{height}" stroke="#dddddd"/>"##, TOP - 8.0));
+        svg.push(format!(r##"<text x="{x:.1}" y="{}" text-anchor="middle" fill="#555555">{label}</text>"##, TOP - 12.0));
+    }
+
+    let mut y = TOP;
+    for (label, bars) in &days {
+        #[allow(clippy::cast_precision_loss)]
+        let group_height = bars.len() as f64 * (BAR_HEIGHT + BAR_GAP);
+        svg.push(format!(r#"<text x="0" y="{:.1}" dominant-baseline="middle">{label}</text>"#, y + group_height / 2.0));
+
+        for bar in bars {
+            let width = bar.nanos.map_or(0.0, |n| scale(n, range, plot_width));
+            if width > 0.0 {
+                svg.push(format!(r#"<rect x="{LABEL_WIDTH}" y="{y:.1}" width="{width:.1}" height="{BAR_HEIGHT}" fill="{}"/>"#, bar.color));
+            }
+            svg.push(format!(
+                r#"<text x="{:.1}" y="{:.1}" dominant-baseline="middle">{}</text>"#,
+                LABEL_WIDTH + width + 4.0,
+                y + BAR_HEIGHT / 2.0,
+                bar.text
+            ));
+            y += BAR_HEIGHT + BAR_GAP;
+        }
+
+        y += DAY_GAP;
+    }
+
+    svg.push("</svg>".into());
+    Some(svg.join("\n") + "\n")
+}
+
+/// Writes the chart to [`CHART_PATH`], returns whether there was anything to draw.
+pub fn write(timings: &[Timings]) -> io::Result<bool> {
+    let Some(svg) = render(timings) else {
+        return Ok(false);
+    };
+
+    if let Some(dir) = Path::new(CHART_PATH).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(CHART_PATH, svg)?;
+    Ok(true)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, scale};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::BenchStats;
+    use std::time::Duration;
+
+    fn timings(day: crate::Day, part_1: Duration, part_2: Option<Duration>) -> Timings {
+        let stats = |d: Duration| BenchStats::from_samples(&[d; 3]);
+        Timings {
+            day,
+            parse: None,
+            part_1: Some(format!("{part_1:.1?}")),
+            part_2: Some(part_2.map_or_else(|| "timed out".into(), |d| format!("{d:.1?}"))),
+            parse_stats: None,
+            part_1_stats: Some(stats(part_1)),
+            part_2_stats: part_2.map(stats),
+            total_nanos: 0_f64,
+            passed: None,
+        }
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        assert_eq!(scale(1e3, (3, 6), 300.0), 0.0);
+        assert_eq!(scale(1e4, (3, 6), 300.0).round(), 100.0);
+        assert_eq!(scale(1e6, (3, 6), 300.0), 300.0);
+    }
+
+    #[test]
+    fn renders_bars_and_axis() {
+        let svg = render(&[
+            timings(day!(1), Duration::from_micros(5), Some(Duration::from_millis(2))),
+            timings(day!(5), Duration::from_millis(40), None),
+        ])
+        .unwrap();
+
+        assert_eq!(svg.starts_with("<svg"), true);
+        assert_eq!(svg.ends_with("</svg>\n"), true);
+        // 2 legend entries and 3 bars, the timed out part only shows its text.
+        assert_eq!(svg.matches("<rect x=").count(), 5);
+        assert_eq!(svg.contains(">Parse</text>"), false);
+        assert_eq!(svg.contains(">timed out</text>"), true);
+        assert_eq!(svg.contains(">Day 05</text>"), true);
+        // decades from 1µs to 100ms.
+        assert_eq!(svg.contains(">1µs</text>"), true);
+        assert_eq!(svg.contains(">100ms</text>"), true);
+        assert_eq!(svg.contains(">1s</text>"), false);
+    }
+
+    #[test]
+    fn renders_nothing_without_timings() {
+        assert_eq!(render(&[]), None);
+    }
+}
@@ -1,39 +1,164 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+use std::{io, thread};
 
 use crate::template::{
+    answers::{self, Answers},
+    bench_history::{self, Run},
+    benchmark_chart,
+    config::{self, Config},
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    report::{self, ReportEntry, ReportOptions, Status},
+    runner::{BenchStats, PartResult, PARSE_STEP},
+    get_data_dir, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
+use crate::{all_days, Day, PuzzleId, Year};
+
+use child_commands::{Builds, Runner};
+
+/// Runs the solutions of the selected days of a year. Solutions run in-process in the runner, which is built with the profile
+/// `is_release` asks for, unless `is_isolated` is set or one of the solutions does not compile, which keeps the runner from building.
+/// Each day then runs in its own child process, a day that does not compile is reported on its own.
+/// Days with a timeout (`timeout`, or their own one in the config) always run in a child process, which is killed on timeout.
+/// With more than one job, days run in parallel child processes and their output is printed in day order.
+/// Timed runs stay sequential unless `is_parallel_timing` is set, so the days do not slow each other down.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub fn handle(
+    year: Year,
+    days: &[Day],
+    is_release: bool,
+    is_timed: bool,
+    is_isolated: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+    is_parallel_timing: bool,
+    report: Option<ReportOptions>,
+) {
     let mut timings: Vec<Timings> = vec![];
+    let mut entries: Vec<ReportEntry> = vec![];
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+    let config = config::load(year).unwrap_or_else(|e| {
+        eprintln!("Failed to load config: {e}");
+        Config::default()
+    });
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    let jobs = if is_timed && jobs > 1 && !is_parallel_timing {
+        println!("{ANSI_ITALIC}Timing days one at a time, pass --parallel-timing to time them in parallel.{ANSI_RESET}\n");
+        1
+    } else {
+        jobs
+    };
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let bin_args = if is_timed { vec!["--time".to_string()] } else { vec![] };
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+    let needs_child = |day: Day| is_isolated || jobs > 1 || config.timeout(day, timeout).is_some();
+
+    let mut runner = if days.iter().any(|&day| !needs_child(day)) {
+        Runner::start(is_release, is_timed)
+            .inspect_err(|e| println!("{ANSI_ITALIC}{e}, running each day in its own process.{ANSI_RESET}\n"))
+            .ok()
+    } else {
+        None
+    };
+
+    // build all bins at once when some of the days run in a child process, instead of once for each of them.
+    let has_runner = runner.is_some();
+    let is_child = |day: Day| !has_runner || needs_child(day);
+    let builds = if days.iter().any(|&day| is_child(day)) {
+        Builds::all(year, days, is_release).unwrap_or_else(|e| {
+            eprintln!("Failed to build solutions: {e}");
+            Builds::on_demand(is_release)
+        })
+    } else {
+        Builds::on_demand(is_release)
+    };
+
+    let mut record = |id: PuzzleId, outcome: Result<Option<Vec<PartResult>>, Error>| match outcome {
+        Ok(Some(mut results)) => {
+            // a bin that crashed outside of its parts did not report them.
+            for part in [1, 2] {
+                if !results.iter().any(|r| r.part == part) {
+                    results.push(PartResult::failed(part, "the solution exited without a result".into()));
+                }
+            }
+
+            let day_entries = report_entries(id, &results);
+            let mut timing = timings_from_results(id.day, &results);
+            timing.passed = passed(&day_entries);
+            timings.push(timing);
+            entries.extend(day_entries);
         }
-    });
+        Ok(None) => println!("Not solved."),
+        Err(e) => entries.extend(report_entries(id, &failed_results(&e))),
+    };
+
+    if jobs > 1 {
+        in_day_order(
+            days,
+            jobs,
+            |day| child_commands::run_solution_buffered(PuzzleId::new(year, day), &builds, &bin_args, config.timeout(day, timeout)),
+            |i, day, (outcome, output)| {
+                print_header(i, day);
+                print!("{output}");
+                record(PuzzleId::new(year, day), outcome);
+            },
+        );
+    } else {
+        days.iter().enumerate().for_each(|(i, &day)| {
+            let id = PuzzleId::new(year, day);
+            print_header(i, day);
+
+            match runner.as_mut() {
+                Some(runner) if !is_child(day) => record(id, runner.run(id)),
+                _ => record(id, child_commands::run_solution(id, &builds, &bin_args, config.timeout(day, timeout))),
+            }
+        });
+    }
+
+    let failed = entries.iter().filter(|e| matches!(e.status, Status::Failed | Status::Errored)).count();
+    if failed > 0 {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET} {failed} part(s) did not produce the known answer.");
+    }
+
+    let mut timed_out: Vec<String> = entries.iter().filter(|e| e.status == Status::TimedOut).map(|e| format!("{:02}", e.day)).collect();
+    timed_out.dedup();
+    if !timed_out.is_empty() {
+        println!("\n{ANSI_BOLD}Timed out:{ANSI_RESET} day {}", timed_out.join(", "));
+    }
+
+    if let Some(report) = report {
+        write_report(&report, &entries);
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+        // only release builds are worth comparing.
+        if is_release && !timings.is_empty() {
+            match bench_history::append(year, &Run::new(&timings)) {
+                Ok(()) => println!("Recorded benchmarks in \"{}\".", bench_history::get_path_for_history(year)),
+                Err(e) => eprintln!("Failed to record benchmarks: {e}"),
+            }
+        }
+
+        if is_release && days.len() < all_days().count() {
+            println!("Only some days were run, the README benchmarks are left as they are.");
+        } else if is_release {
+            let chart = match benchmark_chart::write(&timings) {
+                Ok(true) => Some(benchmark_chart::CHART_PATH),
+                Ok(false) => None,
+                Err(e) => {
+                    eprintln!("Failed to write benchmarks chart: {e}");
+                    None
+                }
+            };
+
+            match readme_benchmarks::update(year, timings, total_millis, &config.readme, chart) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -43,9 +168,55 @@ pub fn handle(is_release: bool, is_timed: bool) {
     }
 }
 
+fn print_header(i: usize, day: Day) {
+    if i > 0 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Runs `run` for each day on `jobs` threads, and hands the results to `done` in day order,
+/// each one as soon as the days before it are done.
+fn in_day_order<T: Send>(days: &[Day], jobs: usize, run: impl Fn(Day) -> T + Sync, mut done: impl FnMut(usize, Day, T)) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (sender, next, run) = (sender.clone(), &next, &run);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                if sender.send((i, run(day))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut finished = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&finished) {
+                done(finished, days[finished], result);
+                finished += 1;
+            }
+        }
+    });
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    /// The solution bin of a day did not compile, with the messages of the compiler.
+    Build(Day, String),
+    /// The runner could not be built or started.
+    Runner(String),
     Parser(String),
     IO(io::Error),
 }
@@ -56,48 +227,215 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution"),
+            Error::Build(day, _) => write!(f, "day {day} did not compile"),
+            Error::Runner(e) => write!(f, "could not start the runner: {e}"),
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "could not run solution: {e}"),
+        }
+    }
+}
+
+/// The name of the solution bin of a puzzle, e.g. `2023_01`.
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_bin_name(id: PuzzleId) -> String {
+    format!("{}_{}", id.year, id.day)
+}
+
+#[must_use]
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(id))
+}
+
+#[must_use]
+pub fn get_path_for_input(id: PuzzleId) -> String {
+    format!("{}/{}.txt", get_data_dir(id.year, "inputs"), id.day)
+}
+
+/// Removes the colors of compiler messages, which are rendered for the terminal.
+fn strip_ansi(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // an escape sequence ends with its first letter, e.g. `\x1b[1;31m`.
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// Prints why a day could not run, and returns both of its parts as failed with that error.
+/// A day that did not compile fails with its compiler messages.
+pub(super) fn failed_results(e: &Error) -> Vec<PartResult> {
+    let error = match e {
+        Error::Build(_, messages) => {
+            eprintln!("Failed to compile solution.");
+            strip_ansi(messages)
+        }
+        e => {
+            eprintln!("Failed to run solution: {e}");
+            e.to_string()
+        }
+    };
+    vec![PartResult::failed(1, error.clone()), PartResult::failed(2, error)]
+}
+
+/// Builds the report entries of a day, checking the answers against the known-answer store.
+/// The parse step has no answer to check and is left out.
+pub(super) fn report_entries(id: PuzzleId, results: &[PartResult]) -> Vec<ReportEntry> {
+    let known = answers::load(id).unwrap_or_else(|e| {
+        eprintln!("Failed to load known answers: {e}");
+        Answers::default()
+    });
+    results
+        .iter()
+        .filter(|r| r.part != PARSE_STEP)
+        .map(|r| ReportEntry::new(id.day, r, known.part(r.part).map(String::from)))
+        .collect()
+}
+
+pub(super) fn write_report(report: &ReportOptions, entries: &[ReportEntry]) {
+    match report::write(report, entries) {
+        Ok(()) => println!("Successfully wrote report to \"{}\".", report.path.display()),
+        Err(e) => {
+            eprintln!("Failed to write report: {e}");
+        }
+    }
+}
+
+/// Collects the timings of the benched parts of a day, and of its parse step if it has one.
+/// Parts stopped by a timeout show as "timed out".
+pub(super) fn timings_from_results(day: Day, results: &[PartResult]) -> Timings {
+    let timed = |part: u8| {
+        results
+            .iter()
+            .find(|r| r.part == part && (r.answer.is_some() || part == PARSE_STEP) && r.stats.samples > 1)
+            .map(|r| r.stats)
+    };
+    let (parse_stats, part_1_stats, part_2_stats) = (timed(PARSE_STEP), timed(1), timed(2));
+
+    let timing = |part: u8, stats: Option<BenchStats>| {
+        if results.iter().any(|r| r.part == part && r.timed_out) {
+            return Some("timed out".to_string());
+        }
+        stats.map(|s| format!("{:.1?}", s.median))
+    };
+
+    Timings {
+        day,
+        parse: parse_stats.map(|s| format!("{:.1?}", s.median)),
+        part_1: timing(1, part_1_stats),
+        part_2: timing(2, part_2_stats),
+        parse_stats,
+        part_1_stats,
+        part_2_stats,
+        #[allow(clippy::cast_precision_loss)]
+        total_nanos: [parse_stats, part_1_stats, part_2_stats].iter().flatten().map(|s| s.median.as_nanos() as f64).sum(),
+        passed: None,
+    }
+}
+
+/// Whether the parts of a day produced their known answers, [`None`] if none of them is known.
+fn passed(entries: &[ReportEntry]) -> Option<bool> {
+    if entries.iter().any(|e| matches!(e.status, Status::Failed | Status::Errored | Status::TimedOut)) {
+        Some(false)
+    } else if entries.iter().any(|e| e.status == Status::Passed) {
+        Some(true)
+    } else {
+        None
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading the records they output.
+pub(super) mod child_commands {
+    use super::{get_bin_name, get_path_for_bin, Error};
+    use crate::template::protocol::{PartRecord, END_OF_DAY};
+    use crate::template::runner::{format_part_result, PartResult};
+    use crate::{Day, PuzzleId, Year};
+    use serde::Deserialize;
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        io::{self, BufRead, BufReader, Lines, Write},
+        path::{Path, PathBuf},
+        process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+        sync::{Arc, Mutex},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// Where the output of a solution bin goes.
+    #[derive(Clone)]
+    pub enum Output {
+        /// Printed as it arrives.
+        Print,
+        /// Collected, to be printed once the day is done.
+        Buffer(Arc<Mutex<String>>),
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    impl Output {
+        fn out(&self, s: &str) {
+            match self {
+                Output::Print => print!("{s}"),
+                Output::Buffer(buffer) => buffer.lock().unwrap().push_str(s),
+            }
+        }
 
-        if is_release {
-            args.push("--release");
+        fn err(&self, s: &str) {
+            match self {
+                Output::Print => eprint!("{s}"),
+                Output::Buffer(buffer) => buffer.lock().unwrap().push_str(s),
+            }
         }
+    }
+
+    /// Run the solution bin for a given puzzle, returns [`None`] if the day has not been scaffolded yet.
+    /// `bin_args` are passed on to the solution bin. The bin is killed once `timeout` has passed,
+    /// the parts that did not finish by then are reported as timed out.
+    pub fn run_solution(id: PuzzleId, builds: &Builds, bin_args: &[String], timeout: Option<Duration>) -> Result<Option<Vec<PartResult>>, Error> {
+        run_solution_to(id, builds, bin_args, timeout, &Output::Print)
+    }
+
+    /// Same as [`run_solution`], but returns the output of the bin instead of printing it.
+    pub fn run_solution_buffered(
+        id: PuzzleId,
+        builds: &Builds,
+        bin_args: &[String],
+        timeout: Option<Duration>,
+    ) -> (Result<Option<Vec<PartResult>>, Error>, String) {
+        let buffer = Arc::new(Mutex::new(String::new()));
+        let result = run_solution_to(id, builds, bin_args, timeout, &Output::Buffer(buffer.clone()));
+        let output = buffer.lock().unwrap().clone();
+        (result, output)
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    fn run_solution_to(
+        id: PuzzleId,
+        builds: &Builds,
+        bin_args: &[String],
+        timeout: Option<Duration>,
+        output: &Output,
+    ) -> Result<Option<Vec<PartResult>>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !builds.has_bin(id) {
+            return Ok(None);
         }
 
+        let executable = builds.executable(id, output)?;
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr and any stdout line that is not a record, print the records as they arrive.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = Command::new(executable)
+            .args(["--format", "json"])
+            .args(bin_args)
+            .env("AOC_YEAR", id.year.to_string())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -105,150 +443,424 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
+        let stderr_output = output.clone();
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                stderr_output.err(&format!("{}\n", line.unwrap()));
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stdout_output = output.clone();
+        let stdout_thread = thread::spawn(move || {
+            read_records(
+                stdout.lines().map(Result::unwrap),
+                |result| stdout_output.out(&format_part_result(result)),
+                |line| stdout_output.out(&format!("{line}\n")),
+            )
+        });
+
+        let is_timed_out = wait_with_timeout(&mut cmd, timeout)?;
+
+        let mut results = stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
+
+        if let (true, Some(timeout)) = (is_timed_out, timeout) {
+            for part in [1, 2] {
+                if !results.iter().any(|r| r.part == part) {
+                    let result = PartResult::timed_out(part, timeout);
+                    output.out(&format_part_result(&result));
+                    results.push(result);
+                }
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        Ok(Some(results))
+    }
 
-        Ok(output)
+    /// The part of a `cargo build --message-format json` line that we are interested in.
+    #[derive(Deserialize)]
+    struct BuildMessage {
+        target: Option<BuildTarget>,
+        executable: Option<PathBuf>,
+        message: Option<Diagnostic>,
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    #[derive(Deserialize)]
+    struct BuildTarget {
+        name: String,
+    }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    #[derive(Deserialize)]
+    struct Diagnostic {
+        rendered: Option<String>,
+    }
+
+    /// The executables of the solution bins. `all` builds the bins of all its days with a single `cargo build` up front,
+    /// bins that were not built that way are built on their own when they are run.
+    #[derive(Debug, Default)]
+    pub struct Builds {
+        is_release: bool,
+        /// Set when the bins were built from another checkout, whose bins are never built on demand.
+        checkout: Option<PathBuf>,
+        executables: HashMap<String, PathBuf>,
+        /// The compiler messages of the bins that did not compile.
+        errors: HashMap<String, String>,
+    }
+
+    impl Builds {
+        /// Builds each bin when it is run.
+        #[must_use]
+        pub fn on_demand(is_release: bool) -> Self {
+            Self {
+                is_release,
+                ..Self::default()
+            }
+        }
+
+        /// Builds the bins of `days` with a single `cargo build`. Bins that do not compile do not stop the others from being built,
+        /// their compiler messages are kept to be shown with their day. The main binary is left out, it is the one running.
+        pub fn all(year: Year, days: &[Day], is_release: bool) -> Result<Self, Error> {
+            Self::build(Command::new("cargo"), None, year, days, is_release)
+        }
+
+        /// Same as [`Builds::all`], for another checkout of the repository in `dir`, e.g. a git worktree.
+        /// Builds into `target_dir` without network access, the dependencies have to be in the local cargo cache.
+        pub fn checkout(dir: &Path, target_dir: &Path, year: Year, days: &[Day], is_release: bool) -> Result<Self, Error> {
+            let mut cargo = Command::new("cargo");
+            cargo.current_dir(dir).env("CARGO_TARGET_DIR", target_dir).env("CARGO_NET_OFFLINE", "true");
+            Self::build(cargo, Some(dir), year, days, is_release)
+        }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+        fn build(mut cargo: Command, checkout: Option<&Path>, year: Year, days: &[Day], is_release: bool) -> Result<Self, Error> {
+            let mut builds = Self::on_demand(is_release);
+            builds.checkout = checkout.map(Path::to_path_buf);
+
+            let names: Vec<String> = days
+                .iter()
+                .map(|&day| PuzzleId::new(year, day))
+                .filter(|&id| builds.has_bin(id))
+                .map(get_bin_name)
+                .collect();
+            if names.is_empty() {
+                return Ok(builds);
+            }
+
+            let mut args = vec!["build", "--quiet", "--keep-going", "--message-format", "json-diagnostic-rendered-ansi"];
+            for name in &names {
+                args.extend(["--bin", name]);
+            }
+
+            if is_release {
+                args.push("--release");
+            }
+
+            // errors that do not belong to a bin, e.g. of the library, show when the day is built on its own.
+            let output = cargo.args(&args).stderr(Stdio::null()).output()?;
+            builds.read_messages(String::from_utf8_lossy(&output.stdout).lines());
+            Ok(builds)
+        }
+
+        fn read_messages<'a>(&mut self, lines: impl Iterator<Item = &'a str>) {
+            for message in lines.filter_map(|line| serde_json::from_str::<BuildMessage>(line).ok()) {
+                let Some(target) = message.target else {
+                    continue;
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                if let Some(executable) = message.executable {
+                    self.executables.insert(target.name, executable);
+                } else if let Some(rendered) = message.message.and_then(|m| m.rendered) {
+                    self.errors.entry(target.name).or_default().push_str(&rendered);
                 }
+            }
 
-                timings.total_nanos += nanos;
-            });
+            self.errors.retain(|name, _| !self.executables.contains_key(name));
+        }
+
+        /// Whether the checkout has the module of a puzzle.
+        #[must_use]
+        pub fn has_bin(&self, id: PuzzleId) -> bool {
+            let path = get_path_for_bin(id);
+            match &self.checkout {
+                Some(dir) => dir.join(path).exists(),
+                None => Path::new(&path).exists(),
+            }
+        }
+
+        /// The executable of a day. A day that did not compile up front has its compiler messages written to `output`,
+        /// a day that was not built up front at all is built now.
+        fn executable(&self, id: PuzzleId, output: &Output) -> Result<PathBuf, Error> {
+            let name = get_bin_name(id);
+
+            if let Some(executable) = self.executables.get(&name) {
+                return Ok(executable.clone());
+            }
+
+            if let Some(errors) = self.errors.get(&name) {
+                output.err(errors);
+                return Err(Error::Build(id.day, errors.clone()));
+            }
+
+            // building it now would build the bin of the current checkout.
+            if self.checkout.is_some() {
+                return Err(Error::Build(id.day, "the bin was not built in the checkout".into()));
+            }
+
+            build_solution(id, self.is_release)
+        }
+    }
+
+    /// Builds the solution bin of a day and returns the path of its executable.
+    /// The executable is run directly rather than through `cargo run`, so that killing it on timeout
+    /// does not leave an orphaned process behind.
+    pub fn build_solution(id: PuzzleId, is_release: bool) -> Result<PathBuf, Error> {
+        let name = get_bin_name(id);
+        let mut args = vec!["build", "--quiet", "--color", "always", "--message-format", "json-render-diagnostics", "--bin", &name];
+
+        if is_release {
+            args.push("--release");
+        }
 
-        timings
+        // the compiler messages are kept for the report, as well as printed.
+        let output = Command::new("cargo").args(&args).stderr(Stdio::piped()).output()?;
+        let messages = String::from_utf8_lossy(&output.stderr);
+        eprint!("{messages}");
+
+        if !output.status.success() {
+            return Err(Error::Build(id.day, messages.into_owned()));
+        }
+
+        read_executable(&output.stdout).ok_or_else(|| Error::Parser(format!("cargo did not report an executable for day {}", id.day)))
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// The executable reported in the output of `cargo build --message-format json`.
+    fn read_executable(stdout: &[u8]) -> Option<PathBuf> {
+        String::from_utf8_lossy(stdout)
+            .lines()
+            .filter_map(|line| serde_json::from_str::<BuildMessage>(line).ok())
+            .find_map(|message| message.executable)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+    /// The runner, which has the solutions of every day compiled in and runs the days it is sent one after the other
+    /// in a single process, see [`registry::serve`](crate::template::registry::serve).
+    pub struct Runner {
+        child: Child,
+        stdin: Option<ChildStdin>,
+        stdout: Lines<BufReader<ChildStdout>>,
+    }
+
+    impl Runner {
+        /// Builds and starts the runner, which benches the parts if `is_timed` is set.
+        /// The runner does not build as soon as one of the solutions does not compile.
+        pub fn start(is_release: bool, is_timed: bool) -> Result<Self, Error> {
+            let mut args = vec!["build", "--quiet", "--message-format", "json", "--bin", "runner"];
+
+            if is_release {
+                args.push("--release");
+            }
+
+            // the compiler messages show when the days that do not compile are built on their own.
+            let output = Command::new("cargo").args(&args).stderr(Stdio::null()).output()?;
+            let executable = read_executable(&output.stdout)
+                .filter(|_| output.status.success())
+                .ok_or_else(|| Error::Runner("the solutions did not build".into()))?;
+
+            let mut child = Command::new(executable)
+                .args(if is_timed { vec!["--time"] } else { vec![] })
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::inherit())
+                .spawn()?;
+
+            let stdin = child.stdin.take().ok_or(Error::BrokenPipe)?;
+            let stdout = BufReader::new(child.stdout.take().ok_or(Error::BrokenPipe)?).lines();
+
+            Ok(Self {
+                child,
+                stdin: Some(stdin),
+                stdout,
+            })
+        }
+
+        /// Runs the solution of a puzzle and prints its results as they arrive, like [`run_solution`].
+        /// Returns [`None`] if the day has not been scaffolded yet.
+        pub fn run(&mut self, id: PuzzleId) -> Result<Option<Vec<PartResult>>, Error> {
+            if !Path::new(&get_path_for_bin(id)).exists() {
+                return Ok(None);
+            }
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+            let stdin = self.stdin.as_mut().ok_or(Error::BrokenPipe)?;
+            writeln!(stdin, "{id}")?;
+            stdin.flush()?;
+
+            // a runner that exits in the middle of a day, e.g. on a stack overflow, leaves the parts it did not finish without a result.
+            let lines = self.stdout.by_ref().map_while(Result::ok).take_while(|line| line != END_OF_DAY);
+            Ok(Some(read_records(
+                lines,
+                |result| print!("{}", format_part_result(result)),
+                |line| println!("{line}"),
+            )))
+        }
+    }
 
-        Some((str_timing, parsed_timing))
+    impl Drop for Runner {
+        fn drop(&mut self) {
+            // the runner exits once its stdin is closed.
+            drop(self.stdin.take());
+            let _ = self.child.wait();
+        }
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Waits for a child process to exit, killing it once `timeout` has passed. Returns whether it was killed.
+    pub fn wait_with_timeout(child: &mut Child, timeout: Option<Duration>) -> io::Result<bool> {
+        let Some(timeout) = timeout else {
+            child.wait()?;
+            return Ok(false);
+        };
+
+        let deadline = Instant::now() + timeout;
+        while child.try_wait()?.is_none() {
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Ok(true);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        Ok(false)
+    }
+
+    /// Collect the records from the output of a solution binary.
+    fn read_records(
+        lines: impl Iterator<Item = String>,
+        on_record: impl Fn(&PartResult),
+        on_other: impl Fn(&str),
+    ) -> Vec<PartResult> {
+        let mut results = vec![];
+
+        for line in lines {
+            match PartRecord::parse(&line) {
+                Some(record) => {
+                    let result = PartResult::from(&record);
+                    on_record(&result);
+                    results.push(result);
+                }
+                None => on_other(&line),
+            }
+        }
+
+        results
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::{read_records, wait_with_timeout, Builds};
+        use crate::template::protocol::PartRecord;
+        use crate::template::runner::{BenchStats, PartResult};
+        use std::path::PathBuf;
+        use std::process::Command;
+        use std::time::{Duration, Instant};
+
+        fn record_line(part: u8, answer: &str) -> String {
+            PartRecord::from(&PartResult {
+                part,
+                answer: Some(answer.into()),
+                error: None,
+                timed_out: false,
+                stats: BenchStats::from_samples(&[Duration::from_millis(2)]),
+            })
+            .to_json()
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let lines = vec![record_line(1, "0"), record_line(2, "10"), String::new()];
+            let res = read_records(lines.into_iter(), |_| {}, |_| {});
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].answer.as_deref(), Some("0"));
+            assert_eq!(res[1].part, 2);
+            assert_eq!(res[1].stats.median, Duration::from_millis(2));
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn test_patterns_in_output() {
+            let lines = vec![
+                "[src/bin/08.rs:41:3] &solutions = [".into(),
+                "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
+                record_line(1, "line 1\nline 2 (2s @ 5 samples)"),
+            ];
+            let res = read_records(lines.into_iter(), |_| {}, |_| {});
+            assert_eq!(res.len(), 1);
+            assert_eq!(res[0].answer.as_deref(), Some("line 1\nline 2 (2s @ 5 samples)"));
+        }
+
+        #[test]
+        fn test_timeout_kills_child() {
+            let timer = Instant::now();
+            let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+            assert_eq!(wait_with_timeout(&mut child, Some(Duration::from_millis(50))).unwrap(), true);
+            assert_eq!(timer.elapsed() < Duration::from_secs(5), true);
+
+            let mut child = Command::new("true").spawn().unwrap();
+            assert_eq!(wait_with_timeout(&mut child, Some(Duration::from_secs(5))).unwrap(), false);
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            let lines = vec!["thread 'main' panicked".into(), String::new()];
+            let res = read_records(lines.into_iter(), |_| {}, |_| {});
+            assert_eq!(res.is_empty(), true);
+        }
+
+        #[test]
+        fn test_build_messages() {
+            let lines = [
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#,
+                r#"{"reason":"compiler-message","target":{"name":"2023_02","kind":["bin"]},"message":{"level":"warning","rendered":"warning: unused variable\n"}}"#,
+                r#"{"reason":"compiler-artifact","target":{"name":"2023_02","kind":["bin"]},"executable":"target/debug/2023_02"}"#,
+                r#"{"reason":"compiler-message","target":{"name":"2023_03","kind":["bin"]},"message":{"level":"error","rendered":"error: expected `;`\n"}}"#,
+                r#"{"reason":"compiler-message","target":{"name":"2023_03","kind":["bin"]},"message":{"level":"error","rendered":"error: aborting\n"}}"#,
+                r#"{"reason":"build-finished","success":false}"#,
+            ];
+            let mut builds = Builds::on_demand(true);
+            builds.read_messages(lines.into_iter());
+
+            assert_eq!(builds.executables.len(), 1);
+            assert_eq!(builds.executables["2023_02"], PathBuf::from("target/debug/2023_02"));
+            assert_eq!(builds.errors.len(), 1);
+            assert_eq!(builds.errors["2023_03"], "error: expected `;`\nerror: aborting\n");
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{in_day_order, strip_ansi};
+    use crate::Day;
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn hands_out_results_in_day_order() {
+        let days: Vec<Day> = (1..=6).map(|d| Day::new(d).unwrap()).collect();
+        let mut finished = vec![];
+
+        // later days finish first.
+        in_day_order(
+            &days,
+            3,
+            |day| {
+                thread::sleep(Duration::from_millis(u64::from(7 - day.into_inner()) * 10));
+                day.into_inner()
+            },
+            |i, day, result| finished.push((i, day.into_inner(), result)),
+        );
+
+        assert_eq!(finished, vec![(0, 1, 1), (1, 2, 2), (2, 3, 3), (3, 4, 4), (4, 5, 5), (5, 6, 6)]);
+    }
+
+    #[test]
+    fn strips_compiler_colors() {
+        assert_eq!(strip_ansi("\x1b[0m\x1b[1m\x1b[38;5;9merror\x1b[0m: expected `;`\n"), "error: expected `;`\n");
+        assert_eq!(strip_ansi("no colors"), "no colors");
+    }
+}
@@ -0,0 +1,298 @@
+/// Compares the benchmarks of two git revisions. Each revision is checked out into a temporary `git worktree`,
+/// its bins are built there and timed on the inputs of the current checkout, the same way `cargo time` does.
+use std::{
+    env,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use crate::template::{
+    readme_benchmarks::Timings,
+    runner::{BenchStats, PARSE_STEP},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{Day, PuzzleId, Year};
+
+use super::all::child_commands::{self, Builds};
+use super::all::{get_path_for_bin, timings_from_results};
+
+#[derive(Debug)]
+pub enum Error {
+    Git(String),
+    /// The bins of a revision could not be built at all, as opposed to single days that do not compile.
+    Build(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Git(e) => write!(f, "git failed: {e}"),
+            Error::Build(e) => write!(f, "could not build the solutions: {e}"),
+            Error::IO(e) => write!(f, "could not run git: {e}"),
+        }
+    }
+}
+
+/// Benchmarks the days at both revisions and prints their timings side by side, with the speedup of `after` over `before`.
+/// Exits with an error if a day could not be benchmarked at one of the revisions.
+pub fn handle(year: Year, days: &[Day], before: &str, after: &str) {
+    let benches = [before, after].map(|rev| {
+        bench_revision(year, days, rev).unwrap_or_else(|e| {
+            eprintln!("Failed to benchmark {rev}: {e}");
+            process::exit(1);
+        })
+    });
+
+    println!();
+    for line in format_table(before, after, &benches[0].timings, &benches[1].timings) {
+        println!("{line}");
+    }
+
+    let problems = find_problems(year, [(before, &benches[0]), (after, &benches[1])]);
+    if !problems.is_empty() {
+        eprintln!();
+        for problem in &problems {
+            eprintln!("{problem}");
+        }
+        process::exit(1);
+    }
+}
+
+/// The outcome of benchmarking the days at a revision.
+#[derive(Default)]
+struct Bench {
+    timings: Vec<Timings>,
+    /// The days that have a bin at the revision, but could not be timed, with the reason.
+    failed: Vec<(Day, String)>,
+    /// The days that have no bin at the revision.
+    missing: Vec<Day>,
+}
+
+/// Builds and times the days at a revision, in a worktree that is removed afterwards.
+fn bench_revision(year: Year, days: &[Day], rev: &str) -> Result<Bench, Error> {
+    let worktree = Worktree::add(rev)?;
+
+    // both revisions share a target dir, so that the dependencies are only built once.
+    let target_dir = env::current_dir()?.join("target").join("bench-compare");
+
+    println!("{ANSI_BOLD}{rev}{ANSI_RESET} {ANSI_ITALIC}building...{ANSI_RESET}");
+    // never falls back to building on demand, that would time the current checkout instead of `rev`.
+    let builds = Builds::checkout(&worktree.path, &target_dir, year, days, true).map_err(|e| Error::Build(e.to_string()))?;
+
+    let mut bench = Bench::default();
+    for &day in days {
+        let id = PuzzleId::new(year, day);
+        if !builds.has_bin(id) {
+            bench.missing.push(day);
+            continue;
+        }
+
+        println!("{ANSI_BOLD}{rev}{ANSI_RESET} {ANSI_ITALIC}timing day {day}...{ANSI_RESET}");
+
+        let (outcome, output) = child_commands::run_solution_buffered(id, &builds, &["--time".into()], None);
+        match outcome {
+            // bins that predate `--format json` print their text output instead of records.
+            Ok(Some(results)) if results.is_empty() => bench.failed.push((day, "the bin printed no `--format json` records".into())),
+            Ok(Some(results)) => bench.timings.push(timings_from_results(day, &results)),
+            Ok(None) => bench.missing.push(day),
+            Err(e) => {
+                eprint!("{output}");
+                bench.failed.push((day, e.to_string()));
+            }
+        }
+    }
+
+    Ok(bench)
+}
+
+/// Why days could not be compared: days that have a bin at a revision but could not be timed there, and days
+/// that were timed at one revision but have no bin at the other. Days without a bin at either revision are left out.
+fn find_problems(year: Year, benches: [(&str, &Bench); 2]) -> Vec<String> {
+    let mut problems = vec![];
+
+    for (i, (rev, bench)) in benches.iter().enumerate() {
+        let other = benches[1 - i].1;
+
+        for (day, reason) in &bench.failed {
+            problems.push(format!("{rev}: could not benchmark day {day}, {reason}."));
+        }
+
+        for day in bench.missing.iter().filter(|day| other.timings.iter().any(|t| t.day == **day)) {
+            problems.push(format!("{rev}: could not benchmark day {day}, there is no \"{}\".", get_path_for_bin(PuzzleId::new(year, *day))));
+        }
+    }
+
+    if problems.is_empty() && benches.iter().all(|(_, bench)| bench.timings.is_empty()) {
+        problems.push("None of the selected days could be benchmarked at either revision.".into());
+    }
+
+    problems
+}
+
+/// A temporary `git worktree` of a revision, removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(rev: &str) -> Result<Self, Error> {
+        let commit = git(&["rev-parse", "--verify", "--quiet", &format!("{rev}^{{commit}}")])
+            .map_err(|_| Error::Git(format!("\"{rev}\" is not a known revision")))?;
+
+        let path = env::temp_dir().join(format!("aoc-bench-{}-{}", &commit[..12.min(commit.len())], process::id()));
+        git(&["worktree", "add", "--detach", "--quiet", &path.to_string_lossy(), &commit])?;
+        let worktree = Self { path };
+
+        // Cargo.lock is not committed, the current one keeps the build from having to resolve dependencies online.
+        if Path::new("Cargo.lock").exists() && !worktree.path.join("Cargo.lock").exists() {
+            std::fs::copy("Cargo.lock", worktree.path.join("Cargo.lock"))?;
+        }
+
+        Ok(worktree)
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(e) = git(&["worktree", "remove", "--force", &self.path.to_string_lossy()]) {
+            eprintln!("Failed to remove worktree \"{}\": {e}", self.path.display());
+        }
+    }
+}
+
+/// Runs git, returns its trimmed output.
+fn git(args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        return Err(Error::Git(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The median of each part at both revisions, and how many times faster `after` is.
+fn format_table(before: &str, after: &str, before_timings: &[Timings], after_timings: &[Timings]) -> Vec<String> {
+    let stats = |timings: &[Timings], day: Day, part: u8| -> Option<BenchStats> {
+        let timing = timings.iter().find(|t| t.day == day)?;
+        match part {
+            PARSE_STEP => timing.parse_stats,
+            1 => timing.part_1_stats,
+            _ => timing.part_2_stats,
+        }
+    };
+
+    let mut days: Vec<Day> = before_timings.iter().chain(after_timings).map(|t| t.day).collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let width = before.len().max(after.len()).max(10);
+    let mut lines = vec![format!("{:<6} {:<6} {before:>width$} {after:>width$} {:>8}", "Day", "Part", "Speedup")];
+
+    for day in days {
+        for part in [PARSE_STEP, 1, 2] {
+            let (old, new) = (stats(before_timings, day, part), stats(after_timings, day, part));
+            if old.is_none() && new.is_none() {
+                continue;
+            }
+
+            let median = |s: Option<BenchStats>| s.map_or_else(|| "-".into(), |s| format!("{:.1?}", s.median));
+            let speedup = match (old, new) {
+                (Some(old), Some(new)) if !new.median.is_zero() => format!("{:.2}×", old.median.as_secs_f64() / new.median.as_secs_f64()),
+                _ => "-".into(),
+            };
+            let part = if part == PARSE_STEP { "Parse".into() } else { part.to_string() };
+
+            lines.push(format!("{:<6} {part:<6} {:>width$} {:>width$} {speedup:>8}", day.to_string(), median(old), median(new)));
+        }
+    }
+
+    lines
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_problems, format_table, Bench};
+    use crate::{day, year};
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::BenchStats;
+    use std::time::Duration;
+
+    fn timings(day: crate::Day, part_1_millis: u64, part_2_millis: Option<u64>) -> Timings {
+        let stats = |millis: u64| BenchStats::from_samples(&[Duration::from_millis(millis); 3]);
+        Timings {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: Some(stats(part_1_millis)),
+            part_2_stats: part_2_millis.map(stats),
+            total_nanos: 0_f64,
+            passed: None,
+        }
+    }
+
+    #[test]
+    fn formats_side_by_side() {
+        let before = [timings(day!(7), 8, Some(9)), timings(day!(8), 4, None)];
+        let after = [timings(day!(7), 4, Some(9)), timings(day!(8), 5, Some(2))];
+
+        assert_eq!(
+            format_table("main", "HEAD", &before, &after),
+            vec![
+                "Day    Part         main       HEAD  Speedup",
+                "07     1           8.0ms      4.0ms    2.00×",
+                "07     2           9.0ms      9.0ms    1.00×",
+                "08     1           4.0ms      5.0ms    0.80×",
+                "08     2               -      2.0ms        -",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_days_missing_at_a_revision() {
+        let before = Bench {
+            missing: vec![day!(6), day!(9)],
+            ..Bench::default()
+        };
+        let after = Bench {
+            timings: vec![timings(day!(6), 4, Some(2))],
+            missing: vec![day!(9)],
+            ..Bench::default()
+        };
+
+        assert_eq!(
+            find_problems(year!(2023), [("53bc7b8", &before), ("HEAD", &after)]),
+            vec!["53bc7b8: could not benchmark day 06, there is no \"./src/bin/2023_06.rs\"."]
+        );
+    }
+
+    #[test]
+    fn reports_days_that_could_not_be_timed() {
+        let before = Bench {
+            failed: vec![(day!(6), "the bin printed no `--format json` records".into())],
+            ..Bench::default()
+        };
+        let after = Bench {
+            timings: vec![timings(day!(6), 4, Some(2))],
+            ..Bench::default()
+        };
+
+        assert_eq!(
+            find_problems(year!(2023), [("main", &before), ("HEAD", &after)]),
+            vec!["main: could not benchmark day 06, the bin printed no `--format json` records."]
+        );
+        assert_eq!(
+            find_problems(year!(2023), [("main", &Bench::default()), ("HEAD", &Bench::default())]),
+            vec!["None of the selected days could be benchmarked at either revision."]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::process;
+
+use crate::template::{
+    bench_history::{self, Comparison, Run, Verdict},
+    runner::PARSE_STEP,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::Year;
+
+/// Compares the latest run in the benchmark history with a baseline: the run before it, or the latest run of the
+/// `baseline` commit. Exits with an error if a part regressed.
+pub fn handle(year: Year, baseline: Option<&str>) {
+    let runs = match bench_history::load(year) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to load benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+
+    let Some((latest, earlier)) = runs.split_last() else {
+        eprintln!("No benchmarks recorded yet, run `cargo time` first.");
+        process::exit(1);
+    };
+
+    let Some(baseline) = find_baseline(earlier, baseline) else {
+        match baseline {
+            Some(commit) => eprintln!("No benchmarks recorded for commit \"{commit}\"."),
+            None => eprintln!("Only one run recorded, run `cargo time` again to compare."),
+        }
+        process::exit(1);
+    };
+
+    println!("{ANSI_BOLD}Baseline:{ANSI_RESET} {}", baseline.label());
+    println!("{ANSI_BOLD}Latest:{ANSI_RESET}   {}", latest.label());
+    if baseline.rustc != latest.rustc {
+        println!("{ANSI_ITALIC}The runs used different rustc versions.{ANSI_RESET}");
+    }
+    println!();
+
+    let comparisons = bench_history::compare(baseline, latest);
+    for comparison in &comparisons {
+        println!("{}", describe(comparison));
+    }
+
+    let regressed = comparisons.iter().filter(|c| c.verdict == Verdict::Regressed).count();
+    if regressed > 0 {
+        println!("\n{ANSI_BOLD}Regressed:{ANSI_RESET} {regressed} part(s) got slower.");
+        process::exit(1);
+    }
+}
+
+/// The run to compare against: the latest one of a commit if given, the one before the latest run otherwise.
+fn find_baseline<'a>(earlier: &'a [Run], commit: Option<&str>) -> Option<&'a Run> {
+    match commit {
+        Some(commit) => earlier.iter().rev().find(|r| r.commit.as_deref().is_some_and(|c| c.starts_with(commit))),
+        None => earlier.last(),
+    }
+}
+
+fn describe(c: &Comparison) -> String {
+    let part = if c.part == PARSE_STEP { "Parse ".to_string() } else { format!("Part {}", c.part) };
+    let verdict = match c.verdict {
+        Verdict::Regressed => "✖ regressed",
+        Verdict::Improved => "✔ improved",
+        Verdict::Unchanged => "~",
+    };
+
+    format!(
+        "Day {:02} · {part}: {:.1?} → {:.1?} ({:+.1}%) {verdict}",
+        c.day,
+        c.baseline,
+        c.latest,
+        c.change * 100_f64
+    )
+}
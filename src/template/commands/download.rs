@@ -1,15 +1,26 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{self, AocClientError};
+use crate::{Day, PuzzleId, Year};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Downloads the input and description of the selected days. A day that fails is reported and the others are
+/// still downloaded, unless the failure would repeat for every day. Exits with an error if any day failed.
+pub fn handle(year: Year, days: &[Day]) {
+    let mut failed = vec![];
+
+    for day in days {
+        if let Err(e) = aoc_client::download(PuzzleId::new(year, *day)) {
+            eprintln!("Failed to download day {day}: {e}");
+            failed.push(*day);
+
+            if matches!(e, AocClientError::SessionNotFound | AocClientError::SessionExpired | AocClientError::RateLimited { .. }) {
+                break;
+            }
+        };
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if !failed.is_empty() {
+        let failed: Vec<String> = failed.iter().map(ToString::to_string).collect();
+        eprintln!("Could not download day(s) {}.", failed.join(", "));
         process::exit(1);
-    };
+    }
 }
@@ -0,0 +1,66 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{answers, examples, get_data_dir};
+use crate::PuzzleId;
+
+/// Writes the examples of a day's puzzle description to `data/<year>/examples`, along with their expected answers.
+/// Existing example files are kept as they are, known expected answers are only completed.
+pub fn handle(id: PuzzleId) {
+    let puzzle_path = examples::get_path_for_puzzle(id);
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        let day = id.day;
+        eprintln!("Could not read \"{puzzle_path}\", run `cargo read {day}` or `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let extracted = examples::extract(&markdown);
+    if extracted.is_empty() {
+        println!("No example found in \"{puzzle_path}\".");
+        return;
+    }
+
+    if let Err(e) = fs::create_dir_all(get_data_dir(id.year, "examples")) {
+        eprintln!("Failed to create examples folder: {e}");
+        process::exit(1);
+    }
+
+    for (i, example) in extracted.iter().enumerate() {
+        let n = i + 1;
+        let example_path = examples::get_path_for_example(id, n);
+
+        if Path::new(&example_path).exists() {
+            println!("Kept existing example file \"{example_path}\"");
+        } else {
+            match fs::write(&example_path, &example.input) {
+                Ok(()) => println!("Created example file \"{example_path}\""),
+                Err(e) => {
+                    eprintln!("Failed to write example file: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+
+        if example.answers.is_empty() {
+            continue;
+        }
+
+        let answers_path = examples::get_path_for_example_answers(id, n);
+        let result = answers::load_from(&answers_path).and_then(|mut known| {
+            for part in [1, 2] {
+                if let (None, Some(answer)) = (known.part(part), example.answers.part(part)) {
+                    known.set_part(part, answer);
+                }
+            }
+            answers::save_to(&answers_path, &known)
+        });
+
+        match result {
+            Ok(()) => println!("Recorded expected answers in \"{answers_path}\""),
+            Err(e) => {
+                eprintln!("Failed to record expected answers: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use std::process;
+
+use crate::template::readme_progress;
+use crate::Year;
+
+/// Updates the ⭐️ progress table in the readme from the known-answer store.
+pub fn handle(year: Year) {
+    match readme_progress::update(year) {
+        Ok(stars) => println!("Successfully updated README with progress: {stars} ⭐."),
+        Err(_) => {
+            eprintln!("Failed to update readme with progress, is the \"<!--- progress table --->\" marker in it?");
+            process::exit(1);
+        }
+    }
+}
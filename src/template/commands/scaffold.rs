@@ -1,10 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::commands::all::{get_path_for_bin, get_path_for_input};
+use crate::{Day, PuzzleId, Year};
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
@@ -16,22 +18,8 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
+// Every example in `data/<year>/examples` with an expected answer is tested, see `cargo examples DAY_NUMBER`.
+// Add a `#[cfg(test)]` module below for any other test.
 "#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
@@ -39,56 +27,55 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().write(true).create(true).truncate(false).open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+/// Scaffolds the selected days. Days that already have a module are skipped, a day that fails is reported
+/// and the others are still scaffolded. Exits with an error if any day failed.
+pub fn handle(year: Year, days: &[Day]) {
+    let mut has_failed = false;
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+        if let Err(e) = scaffold(PuzzleId::new(year, *day)) {
+            eprintln!("Failed to scaffold day {day}: {e}");
+            has_failed = true;
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    if has_failed {
+        process::exit(1);
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+fn scaffold(id: PuzzleId) -> Result<(), String> {
+    let day = id.day;
+    let input_path = get_path_for_input(id);
+    let module_path = get_path_for_bin(id);
+
+    let mut file = match safe_create_file(&module_path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            println!("Skipped day {day}, module file \"{module_path}\" already exists.");
+            return Ok(());
         }
-    }
+        Err(e) => return Err(format!("could not create module file: {e}")),
+    };
+
+    file.write_all(MODULE_TEMPLATE.replace("DAY_NUMBER", &day.into_inner().to_string()).as_bytes())
+        .map_err(|e| format!("could not write module contents: {e}"))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    create_file(&input_path).map_err(|e| format!("could not create input file: {e}"))?;
+    println!("Created empty input file \"{}\"", &input_path);
 
     println!("---");
+    println!("🎄 Type `cargo examples {}` to extract the examples once the puzzle is downloaded.", day);
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    Ok(())
 }
@@ -1,31 +1,121 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
-use crate::Day;
+use crate::template::config::{self, Config};
+use crate::template::runner::format_timeout;
+use crate::template::{report::ReportOptions, InputSource};
+use crate::{Day, PuzzleId, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+use super::all::child_commands::{self, Builds};
+use super::all::{failed_results, report_entries, write_report};
+use super::watch;
 
-    if release {
-        cmd_args.push("--release".to_string());
+/// Runs the solution bins of the selected days one after the other, or keeps re-running a single day with `watch`.
+/// The bins read their input from `input`, the puzzle input by default.
+/// A bin is killed once its timeout has passed: `timeout`, or the day's own one in the config.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    days: &[Day],
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    report: Option<ReportOptions>,
+    watch: bool,
+    input: &InputSource,
+    timeout: Option<Duration>,
+) {
+    if submit_part.is_some() && days.len() != 1 {
+        eprintln!("Submitting requires a single day.");
+        process::exit(1);
     }
 
-    cmd_args.push("--".to_string());
+    if submit_part.is_some() && *input != InputSource::Puzzle {
+        eprintln!("Submitting requires the puzzle input, remove --input or --example.");
+        process::exit(1);
+    }
+
+    if matches!(input, InputSource::File(_) | InputSource::Stdin) && days.len() != 1 {
+        eprintln!("Passing an input requires a single day.");
+        process::exit(1);
+    }
+
+    if watch && (days.len() != 1 || submit_part.is_some() || report.is_some()) {
+        eprintln!("Watching requires a single day, without --submit or --report.");
+        process::exit(1);
+    }
+
+    let mut bin_args = vec![];
 
     if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+        bin_args.push("--submit".to_string());
+        bin_args.push(submit_part.to_string());
     }
 
     if time {
-        cmd_args.push("--time".to_string());
+        bin_args.push("--time".to_string());
+    }
+
+    bin_args.extend(input.to_args());
+
+    let config = config::load(year).unwrap_or_else(|e| {
+        eprintln!("Failed to load config: {e}");
+        Config::default()
+    });
+
+    if watch {
+        watch::handle(PuzzleId::new(year, days[0]), release, &bin_args, config.timeout(days[0], timeout));
+    }
+
+    if let Some(report) = report {
+        // the report needs the results, read them from the records of the solution bins.
+        let mut entries = vec![];
+        for day in days {
+            let id = PuzzleId::new(year, *day);
+            let results = match child_commands::run_solution(id, &Builds::on_demand(release), &bin_args, config.timeout(*day, timeout)) {
+                Ok(results) => results.unwrap_or_default(),
+                Err(e) => failed_results(&e),
+            };
+            entries.extend(report_entries(id, &results));
+        }
+        write_report(&report, &entries);
+        return;
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    for day in days {
+        run(PuzzleId::new(year, *day), release, &bin_args, config.timeout(*day, timeout));
+    }
+}
+
+fn run(id: PuzzleId, release: bool, bin_args: &[String], timeout: Option<Duration>) {
+    let Ok(executable) = child_commands::build_solution(id, release) else {
+        // cargo already printed why the build failed.
+        return;
+    };
+
+    let spawned = Command::new(executable)
+        .args(bin_args)
+        .env("AOC_YEAR", id.year.to_string())
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .spawn();
 
-    cmd.wait().unwrap();
+    let mut cmd = match spawned {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            eprintln!("Failed to run day {}: {e}", id.day);
+            return;
+        }
+    };
+
+    match child_commands::wait_with_timeout(&mut cmd, timeout) {
+        Ok(true) => {
+            if let Some(timeout) = timeout {
+                eprintln!("\nDay {} timed out after {}.", id.day, format_timeout(timeout));
+            }
+        }
+        Ok(false) => {}
+        Err(e) => eprintln!("Failed to wait for day {}: {e}", id.day),
+    }
 }
@@ -0,0 +1,95 @@
+use std::{path::Path, process};
+
+use crate::template::{answers, report::Status, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{all_days, Day, PuzzleId, Year};
+
+use super::all::child_commands::{self, Builds, Runner};
+use super::all::get_path_for_bin;
+
+/// Runs the solutions of every day of a year (or a single one) and checks their answers against the known-answer store.
+/// The solutions run in-process in the runner, or each in its own process if one of them does not compile.
+pub fn handle(year: Year, day: Option<Day>) {
+    let mut mismatches = 0;
+    let mut matches = 0;
+
+    // the solutions run with the profile of this binary, e.g. `cargo verify` runs them in release.
+    let is_release = !cfg!(debug_assertions);
+    let builds = Builds::on_demand(is_release);
+    let mut runner = Runner::start(is_release, false)
+        .inspect_err(|e| println!("{ANSI_ITALIC}{e}, running each day in its own process.{ANSI_RESET}\n"))
+        .ok();
+
+    for day in all_days().filter(|d| day.is_none_or(|day| day == *d)) {
+        let id = PuzzleId::new(year, day);
+        let known = match answers::load(id) {
+            Ok(known) => known,
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                mismatches += 1;
+                continue;
+            }
+        };
+
+        if known.is_empty() || !Path::new(&get_path_for_bin(id)).exists() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let outcome = match runner.as_mut() {
+            Some(runner) => runner.run(id),
+            None => child_commands::run_solution(id, &builds, &[], None),
+        };
+
+        let results = match outcome {
+            Ok(results) => results.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("Failed to run solution: {e}");
+                mismatches += 1;
+                continue;
+            }
+        };
+
+        for result in &results {
+            let Some(expected) = known.part(result.part) else {
+                continue;
+            };
+
+            match Status::of(result, Some(expected)) {
+                Status::Passed => {
+                    matches += 1;
+                    println!("✔ part {} matches", result.part);
+                }
+                Status::Errored => {
+                    mismatches += 1;
+                    println!("✖ part {}: expected {expected}, got an error", result.part);
+                }
+                _ => {
+                    mismatches += 1;
+                    println!(
+                        "✖ part {}: expected {expected}, got {}",
+                        result.part,
+                        result.answer.as_deref().unwrap_or("nothing")
+                    );
+                }
+            }
+        }
+
+        // a solution that crashed outside of its parts did not report them.
+        for part in [1, 2].into_iter().filter(|part| !results.iter().any(|r| r.part == *part)) {
+            if let Some(expected) = known.part(part) {
+                mismatches += 1;
+                println!("✖ part {part}: expected {expected}, got nothing");
+            }
+        }
+
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Verified:{ANSI_RESET} {matches} matching, {mismatches} not matching.");
+
+    if mismatches > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,175 @@
+/// Watch mode of `solve`: re-runs the solution of a day whenever its module, the library sources or its data files change.
+/// Changes are detected by polling the modification times of the watched files, which works on every platform.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    answers::{self, Answers},
+    get_data_dir,
+    report::Status,
+    runner::PartResult,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::PuzzleId;
+
+use super::all::child_commands::{self, Builds};
+use super::all::get_path_for_bin;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Rebuilds and runs the solution of a day on every change, until interrupted.
+pub fn handle(id: PuzzleId, release: bool, bin_args: &[String], timeout: Option<Duration>) -> ! {
+    let mut previous: Vec<PartResult> = vec![];
+    let mut snapshot = vec![];
+    let builds = Builds::on_demand(release);
+
+    loop {
+        let current = take_snapshot(id);
+        if current == snapshot {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        snapshot = current;
+
+        print!("{ANSI_CLEAR}");
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET} {ANSI_ITALIC}watching for changes, press Ctrl+C to stop{ANSI_RESET}", id.day);
+        println!("------");
+
+        let results = match child_commands::run_solution(id, &builds, bin_args, timeout) {
+            Ok(results) => results.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("Failed to run solution: {e}");
+                vec![]
+            }
+        };
+
+        let known = answers::load(id).unwrap_or_else(|e| {
+            eprintln!("Failed to load known answers: {e}");
+            Answers::default()
+        });
+
+        println!();
+        for line in summarize(&results, &previous, &known) {
+            println!("{line}");
+        }
+
+        if !results.is_empty() {
+            previous = results;
+        }
+    }
+}
+
+/// Describes the answer of each part compared to the previous run and to the known answer.
+fn summarize(results: &[PartResult], previous: &[PartResult], known: &Answers) -> Vec<String> {
+    [1, 2]
+        .iter()
+        .map(|&part| {
+            let result = results.iter().find(|r| r.part == part);
+            let answer = result.and_then(|r| r.answer.as_deref());
+            let before = previous.iter().find(|r| r.part == part).map(|r| r.answer.as_deref());
+            let expected = known.part(part);
+
+            let change = match before {
+                None => String::new(),
+                Some(before) if before == answer => " (unchanged)".into(),
+                Some(before) => format!(" (was {})", before.unwrap_or("✖")),
+            };
+
+            let status = match result.map_or(Status::Unsolved, |r| Status::of(r, expected)) {
+                Status::Passed => "✔ matches the known answer".to_string(),
+                Status::Failed => format!("✖ expected {}", expected.unwrap_or_default()),
+                Status::Unverified => "no known answer".into(),
+                Status::Unsolved => "not solved".into(),
+                Status::TimedOut => result.and_then(|r| r.error.clone()).unwrap_or_default(),
+                Status::Errored => format!("error: {}", result.and_then(|r| r.error.as_deref()).unwrap_or_default()),
+            };
+
+            format!("Part {part}: {}{change} · {status}", answer.unwrap_or("✖"))
+        })
+        .collect()
+}
+
+/// The modification times of the watched files: the module of the day, the library sources
+/// and the data files of the day, e.g. `data/2023/inputs/01.txt` or `data/2023/examples/01-2.txt`.
+fn take_snapshot(id: PuzzleId) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut files = vec![PathBuf::from(get_path_for_bin(id))];
+
+    collect_files(Path::new("src"), &mut files, &|path| {
+        path.extension().is_some_and(|x| x == "rs") && !path.starts_with("src/bin")
+    });
+
+    let day = id.day.to_string();
+    collect_files(Path::new(&get_data_dir(id.year, "")), &mut files, &|path| {
+        let name = path.file_name().and_then(|x| x.to_str()).unwrap_or_default();
+        name.starts_with(&day) && name.ends_with(".txt")
+    });
+
+    files.sort();
+    files
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>, filter: &impl Fn(&Path) -> bool) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files, filter);
+        } else if filter(&path) {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::summarize;
+    use crate::template::answers::Answers;
+    use crate::template::runner::{BenchStats, PartResult};
+    use std::time::Duration;
+
+    fn result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            answer: answer.map(String::from),
+            error: None,
+            timed_out: false,
+            stats: BenchStats::from_samples(&[Duration::from_millis(1)]),
+        }
+    }
+
+    #[test]
+    fn summarizes_first_run() {
+        let known = Answers {
+            part_one: Some("142".into()),
+            part_two: None,
+        };
+        let lines = summarize(&[result(1, Some("142")), result(2, Some("7"))], &[], &known);
+        assert_eq!(lines, vec!["Part 1: 142 · ✔ matches the known answer", "Part 2: 7 · no known answer"]);
+    }
+
+    #[test]
+    fn summarizes_changes() {
+        let known = Answers {
+            part_one: Some("142".into()),
+            part_two: Some("281".into()),
+        };
+        let previous = [result(1, Some("140")), result(2, None)];
+        let lines = summarize(&[result(1, Some("140")), result(2, Some("280"))], &previous, &known);
+        assert_eq!(lines, vec!["Part 1: 140 (unchanged) · ✖ expected 142", "Part 2: 280 (was ✖) · ✖ expected 281"]);
+    }
+}
@@ -0,0 +1,129 @@
+/// Module that reads the settings of a year from `data/<year>/config.toml`.
+/// These are the timeouts of slow days, which override `--timeout`, and the layout of the readme benchmarks:
+///
+/// ```toml
+/// [timeouts]
+/// # seconds
+/// 05 = 300
+///
+/// [readme]
+/// columns = ["parse", "total", "share"]
+/// sorted = true
+/// ```
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path, time::Duration};
+
+use serde::Deserialize;
+
+use crate::template::readme_benchmarks::TableOptions;
+use crate::{Day, Year};
+
+use super::get_data_dir;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse config: {e}"),
+            Error::IO(e) => write!(f, "could not access config file: {e}"),
+        }
+    }
+}
+
+/// The settings of a year.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub timeouts: BTreeMap<Day, Duration>,
+    /// The columns and tables of the readme benchmarks.
+    pub readme: TableOptions,
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    timeouts: BTreeMap<String, u64>,
+    #[serde(default)]
+    readme: TableOptions,
+}
+
+impl Config {
+    /// The timeout of a day: its own one if set, `default` otherwise.
+    #[must_use]
+    pub fn timeout(&self, day: Day, default: Option<Duration>) -> Option<Duration> {
+        self.timeouts.get(&day).copied().or(default)
+    }
+
+    fn from_toml(s: &str) -> Result<Self, Error> {
+        let file: ConfigFile = toml::from_str(s).map_err(|e| Error::Parser(e.to_string()))?;
+
+        let timeouts = file
+            .timeouts
+            .into_iter()
+            .map(|(day, secs)| {
+                let day = day.parse().map_err(|e| Error::Parser(format!("invalid day \"{day}\" in timeouts: {e}")))?;
+                Ok((day, Duration::from_secs(secs)))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self { timeouts, readme: file.readme })
+    }
+}
+
+#[must_use]
+pub fn get_path_for_config(year: Year) -> String {
+    format!("{}/config.toml", get_data_dir(year, ""))
+}
+
+/// Reads the settings of a year, a missing file means the defaults.
+pub fn load(year: Year) -> Result<Config, Error> {
+    let path = get_path_for_config(year);
+    if !Path::new(&path).exists() {
+        return Ok(Config::default());
+    }
+    Config::from_toml(&fs::read_to_string(path)?)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Config;
+    use crate::day;
+    use crate::template::readme_benchmarks::Column;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_timeouts() {
+        let config = Config::from_toml("[timeouts]\n05 = 300\n12 = 5\n").unwrap();
+        let default = Some(Duration::from_secs(10));
+
+        assert_eq!(config.timeout(day!(5), default), Some(Duration::from_secs(300)));
+        assert_eq!(config.timeout(day!(12), None), Some(Duration::from_secs(5)));
+        assert_eq!(config.timeout(day!(1), default), default);
+        assert_eq!(config.timeout(day!(1), None), None);
+    }
+
+    #[test]
+    fn parses_readme_options() {
+        let config = Config::from_toml("[readme]\ncolumns = [\"total\", \"answers\"]\nsorted = true\n").unwrap();
+        assert_eq!(config.readme.columns, vec![Column::Total, Column::Answers]);
+        assert_eq!(config.readme.sorted, true);
+
+        assert_eq!(Config::from_toml("[readme]\nsorted = true\n").unwrap().readme.columns, vec![Column::Parse]);
+        assert_eq!(Config::from_toml("[readme]\ncolumns = [\"speed\"]\n").is_err(), true);
+    }
+
+    #[test]
+    fn errors_on_invalid_days() {
+        assert_eq!(Config::from_toml("[timeouts]\n26 = 300\n").is_err(), true);
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+    }
+}
@@ -0,0 +1,230 @@
+/// Module that extracts the examples of a puzzle from the markdown written by `read` / `download`.
+/// Every fenced code block becomes `data/<year>/examples/NN-<n>.txt`. Answers shown as emphasised code
+/// spans (`*142*`) are recorded as the expected answers of the example in `data/<year>/examples/NN-<n>.toml`.
+use std::fs;
+
+use crate::PuzzleId;
+
+use super::answers::{self, Answers};
+use super::runner::PartOutput;
+use super::get_data_dir;
+
+/// An example input of a puzzle, with the answers the puzzle description gives for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+#[must_use]
+pub fn get_path_for_puzzle(id: PuzzleId) -> String {
+    format!("{}/{}.md", get_data_dir(id.year, "puzzles"), id.day)
+}
+
+/// The path of the `n`-th example of a day, starting at 1.
+#[must_use]
+pub fn get_path_for_example(id: PuzzleId, n: usize) -> String {
+    format!("{}/{}-{n}.txt", get_data_dir(id.year, "examples"), id.day)
+}
+
+/// The path of the expected answers of the `n`-th example of a day.
+#[must_use]
+pub fn get_path_for_example_answers(id: PuzzleId, n: usize) -> String {
+    format!("{}/{}-{n}.toml", get_data_dir(id.year, "examples"), id.day)
+}
+
+/// Runs a part against the `n`-th example of a day and asserts that it returns the expected answer.
+/// The tests generated for every example call this, see the `solution!` macro.
+///
+/// # Panics
+/// When the example or its expected answer can not be read, the part returns an error or the answer does not match.
+pub fn check<R: PartOutput>(func: impl Fn(&str) -> R, id: PuzzleId, n: usize, part: u8) {
+    let input = fs::read_to_string(get_path_for_example(id, n)).expect("could not open example file");
+    let answers = answers::load_from(&get_path_for_example_answers(id, n)).expect("could not read expected answers");
+
+    let expected = answers.part(part).map(String::from);
+    assert!(expected.is_some(), "example {n} has no expected answer for part {part}");
+    let answer = func(&input).to_answer().unwrap_or_else(|e| panic!("example {n}, part {part} returned an error: {e}"));
+    assert_eq!(answer, expected, "wrong answer for example {n}, part {part}");
+}
+
+enum Block<'a> {
+    PartTwo,
+    Paragraph(&'a str),
+    Code(String),
+}
+
+/// Extracts the examples of a puzzle description, in order of appearance.
+///
+/// A code block is an example when the paragraph introducing it mentions one, other blocks illustrate
+/// the puzzle (intermediate states, excerpts of an example, ...) and are skipped.
+/// The last emphasised code span of a paragraph is taken as the answer of the current part for the
+/// preceding example. A paragraph that ends with a colon introduces the example that follows it instead.
+#[must_use]
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let blocks = split_blocks(markdown);
+    let mut examples: Vec<Example> = vec![];
+    let mut pending: Option<String> = None;
+    let mut part = 1;
+
+    for (i, block) in blocks.iter().enumerate() {
+        match block {
+            Block::PartTwo => part = 2,
+            Block::Code(input) => {
+                let answer = pending.take();
+
+                let is_introduced = i > 0 && matches!(blocks[i - 1], Block::Paragraph(text) if text.to_lowercase().contains("example"));
+                let is_excerpt = examples.iter().any(|e| e.input.contains(input.as_str()));
+                if !is_introduced || is_excerpt {
+                    continue;
+                }
+
+                let mut answers = Answers::default();
+                if let Some(answer) = answer {
+                    answers.set_part(part, &answer);
+                }
+                examples.push(Example {
+                    input: input.clone(),
+                    answers,
+                });
+            }
+            Block::Paragraph(text) => {
+                let Some(answer) = emphasised_spans(text).pop() else {
+                    continue;
+                };
+                let introduces_example = text.trim_end().ends_with(':') && matches!(blocks.get(i + 1), Some(Block::Code(_)));
+                if introduces_example {
+                    pending = Some(answer);
+                } else if let Some(example) = examples.last_mut() {
+                    example.answers.set_part(part, &answer);
+                }
+            }
+        }
+    }
+
+    examples
+}
+
+fn split_blocks(markdown: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut code: Option<String> = None;
+
+    for line in markdown.lines() {
+        match code.as_mut() {
+            Some(input) if line == "```" => {
+                // the block ends with the line break of its last line, plus the one closing the fence.
+                let input = input.strip_suffix('\n').unwrap_or(input);
+                blocks.push(Block::Code(input.to_string()));
+                code = None;
+            }
+            Some(input) => {
+                input.push_str(line);
+                input.push('\n');
+            }
+            None if line == "```" => code = Some(String::new()),
+            None if line.starts_with("\\--- Part Two ---") => blocks.push(Block::PartTwo),
+            None if !line.trim().is_empty() => blocks.push(Block::Paragraph(line)),
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+/// Finds the code spans of a line that are emphasised, written as `` `*42*` `` or `` *`42`* ``.
+fn emphasised_spans(line: &str) -> Vec<String> {
+    let mut spans = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("`*").into_iter().chain(rest.find("*`")).min() {
+        let close = if rest[start..].starts_with("`*") { "*`" } else { "`*" };
+        let content_start = start + 2;
+
+        let Some(len) = rest[content_start..].find(close) else {
+            break;
+        };
+
+        let content = &rest[content_start..content_start + len];
+        if !content.is_empty() && content.trim() == content && !content.contains(['`', '*']) {
+            spans.push(content.to_string());
+            rest = &rest[content_start + len + 2..];
+        } else {
+            rest = &rest[content_start..];
+        }
+    }
+
+    spans
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasised_spans, extract};
+
+    #[test]
+    fn finds_emphasised_spans() {
+        assert_eq!(emphasised_spans("values `12` and `38`. Adding these together produces `*142*`."), vec!["142"]);
+        assert_eq!(emphasised_spans("*location `82`* and *`43`*, `*CCC*`"), vec!["43", "CCC"]);
+        assert_eq!(emphasised_spans("(`765` \\* 1 + `220` \\* 2)").is_empty(), true);
+    }
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let markdown = [
+            "\\--- Day 8: Haunted Wasteland ---",
+            "----------",
+            "",
+            "For example:",
+            "",
+            "```",
+            "RL",
+            "",
+            "AAA = (BBB, CCC)",
+            "",
+            "```",
+            "",
+            "You reach `ZZZ` in `*2*` steps.",
+            "",
+            "For example, here is a situation that takes `*6*` steps:",
+            "",
+            "```",
+            "LLR",
+            "",
+            "```",
+            "",
+            "Consider again the first example:",
+            "",
+            "```",
+            "RL",
+            "",
+            "```",
+            "",
+            "The steps look like this:",
+            "",
+            "```",
+            "AAA -> ZZZ",
+            "",
+            "```",
+            "",
+            "\\--- Part Two ---",
+            "----------",
+            "",
+            "In the second example, it now takes `*12*` steps.",
+        ]
+        .join("\n");
+
+        let examples = extract(&markdown);
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "RL\n\nAAA = (BBB, CCC)\n");
+        assert_eq!(examples[0].answers.part(1), Some("2"));
+        assert_eq!(examples[0].answers.part(2), None);
+        assert_eq!(examples[1].input, "LLR\n");
+        assert_eq!(examples[1].answers.part(1), Some("6"));
+        assert_eq!(examples[1].answers.part(2), Some("12"));
+    }
+
+    #[test]
+    fn extracts_nothing_without_examples() {
+        assert_eq!(extract("\\--- Day 1 ---\n\nNo example, answer `*1*`.\n").is_empty(), true);
+    }
+}
@@ -1,36 +1,384 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{all_days, Day, DaySelection, PuzzleId, Year};
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_history;
+pub mod benchmark_chart;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod protocol;
 pub mod readme_benchmarks;
+pub mod readme_progress;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod submissions;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// The year selected with the `AOC_YEAR` environment variable, if any.
+#[must_use]
+pub fn get_year() -> Option<Year> {
+    env::var("AOC_YEAR").ok()?.trim().parse().ok()
+}
+
+/// Identifies the puzzle of a day in `year`, or in the selected year if [`None`].
+/// Exits if no year is selected.
+#[must_use]
+pub fn puzzle_id(day: Day, year: Option<Year>) -> PuzzleId {
+    match year.or_else(get_year) {
+        Some(year) => PuzzleId::new(year, day),
+        None => {
+            eprintln!("No year selected, set the AOC_YEAR environment variable or pass --year.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Resolves a selection of days of a year, based on the scaffolded days and the known-answer store.
+/// `latest` is the last scaffolded day.
+#[must_use]
+pub fn select_days(selection: &DaySelection, year: Year) -> Vec<Day> {
+    let scaffolded = scaffolded_days(year);
+    selection.resolve(scaffolded.iter().max().copied(), &scaffolded, |day| is_solved(PuzzleId::new(year, day)))
+}
+
+/// Resolves a selection of days to scaffold or download, `latest` is the last unlocked day instead,
+/// since the day to fetch is usually not scaffolded yet.
+#[must_use]
+pub fn select_unlocked_days(selection: &DaySelection, year: Year) -> Vec<Day> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let latest = latest_unlocked_day(year, now);
+    if latest.is_none() && selection.contains_latest() {
+        eprintln!("No puzzle of {year} is unlocked yet.");
+    }
+    selection.resolve(latest, &scaffolded_days(year), |day| is_solved(PuzzleId::new(year, day)))
+}
+
+fn scaffolded_days(year: Year) -> Vec<Day> {
+    all_days()
+        .filter(|day| Path::new(&commands::all::get_path_for_bin(PuzzleId::new(year, *day))).exists())
+        .collect()
+}
+
+fn is_solved(id: PuzzleId) -> bool {
+    answers::load(id).is_ok_and(|known| known.part(1).is_some() && known.part(2).is_some())
+}
+
+/// The last day of `year` whose puzzle is unlocked at `now`, the time since the unix epoch.
+/// Puzzles unlock at midnight EST, i.e. 05:00 UTC, from the 1st of December on.
+fn latest_unlocked_day(year: Year, now: Duration) -> Option<Day> {
+    // the days from the epoch to the 1st of December, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = u64::from(year.into_inner());
+    let (era, yoe) = (year / 400, year % 400);
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + 275;
+    let first_unlock = (era * 146_097 + doe - 719_468) * 86_400 + 5 * 3600;
+
+    let days_unlocked = now.as_secs().checked_sub(first_unlock)? / 86_400 + 1;
+    all_days().take_while(|day| u64::from(day.into_inner()) <= days_unlocked).last()
+}
+
+/// The folder of the data files of a year, e.g. `data/2023/inputs`.
+#[must_use]
+pub fn get_data_dir(year: Year, folder: &str) -> String {
+    format!("data/{year}/{folder}")
+}
+
+/// Helper function that reads a text file of the selected year to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    read_puzzle_file(folder, puzzle_id(day, None))
+}
+
+/// Helper function that reads the `n`-th file of a day, e.g. an example written by `cargo examples`.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, n: u8) -> String {
+    let id = puzzle_id(day, None);
+    read_path(&format!("{}/{day}-{n}.txt", get_data_dir(id.year, folder)))
+}
+
+/// Helper function that reads a text file of a puzzle to a string.
+#[must_use]
+pub fn read_puzzle_file(folder: &str, id: PuzzleId) -> String {
+    read_path(&format!("{}/{}.txt", get_data_dir(id.year, folder), id.day))
+}
+
+/// Where a solution binary reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The puzzle input in `data/<year>/inputs`.
+    #[default]
+    Puzzle,
+    /// A file given with `--input <path>`.
+    File(PathBuf),
+    /// The standard input, selected with `--input -`.
+    Stdin,
+    /// The `n`-th example in `data/<year>/examples`, selected with `--example [n]`.
+    Example(usize),
+}
+
+impl InputSource {
+    /// Parse the `--input` and `--example` arguments passed to a solution binary, defaults to [`InputSource::Puzzle`].
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::parse(&args).unwrap_or_else(|| {
+            eprintln!("Unexpected command-line input. Format: --input <path|-> or --example [n]");
+            process::exit(1);
+        })
+    }
+
+    fn parse(args: &[String]) -> Option<Self> {
+        let input = args.iter().position(|x| x == "--input");
+        let example = args.iter().position(|x| x == "--example");
+
+        match (input, example) {
+            (Some(_), Some(_)) => None,
+            (Some(index), None) => match args.get(index + 1)?.as_str() {
+                "-" => Some(Self::Stdin),
+                path if path.starts_with("--") => None,
+                path => Some(Self::File(path.into())),
+            },
+            (None, Some(index)) => match args.get(index + 1) {
+                Some(n) if !n.starts_with("--") => n.parse().ok().filter(|n| *n > 0).map(Self::Example),
+                _ => Some(Self::Example(1)),
+            },
+            (None, None) => Some(Self::Puzzle),
+        }
+    }
+
+    /// The arguments that select this input source in a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+            Self::Example(n) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    /// Reads the input of a puzzle from this source.
+    #[must_use]
+    pub fn read(&self, id: PuzzleId) -> String {
+        match self {
+            Self::Puzzle => read_puzzle_file("inputs", id),
+            Self::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).expect("could not read standard input");
+                input
+            }
+            Self::Example(n) => read_path(&examples::get_path_for_example(id, *n)),
+        }
+    }
+}
+
+fn read_path(path: &str) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = fs::read_to_string(cwd.join(path));
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// The year of a solution bin from its name, e.g. `2023` for `2023_01`, [`None`] for any other binary.
+/// Solution bins are named after their file, `src/bin/<year>_<day>.rs`.
+#[must_use]
+pub const fn bin_year(name: &str) -> Option<Year> {
+    let bytes = name.as_bytes();
+    if bytes.len() != 7 || bytes[4] != b'_' {
+        return None;
+    }
+
+    let mut year: u16 = 0;
+    let mut i = 0;
+    while i < 4 {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    if year < 2015 {
+        return None;
+    }
+    Some(Year::__new_unchecked(year))
+}
+
+/// Whether `year` is the year of a solution bin, from its name. Any year goes for other binaries,
+/// e.g. the runner that has the solutions of every year compiled in.
+#[must_use]
+pub const fn is_bin_year(name: &str, year: u16) -> bool {
+    match bin_year(name) {
+        Some(bin_year) => bin_year.into_inner() == year,
+        None => true,
+    }
+}
+
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
+/// The input is the puzzle input, unless another one is selected, see [`InputSource`].
+/// The year is the one of the file name of the solution, `src/bin/<year>_<day>.rs`, see [`bin_year`].
+/// It can also be given with a `year: <year>` argument, which has to match the file name.
+/// With a `parse: <function>` argument, the input is parsed once and both parts receive a reference
+/// to the parsed value. The parse function is timed on its own, see [`runner::PARSE_STEP`].
+/// Parts return an [`Option`] or a [`Result`], see [`runner::PartOutput`].
+/// The parts are also exposed as the function `RUN`, which `build.rs` registers in the runner for the puzzle of the file, see [`registry`].
+/// In tests, every example of the day with an expected answer is checked, see [`examples::check`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@impl $day:expr, year: [$($year:expr)?], parse: [$($parse:path)?]) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// The year of the current day, from the name of the solution bin.
+        const YEAR: Option<advent_of_code::Year> = advent_of_code::solution!(@year $($year)?);
+
+        /// The parts of the current day, as run in-process by the runner. Prints nothing, the runner reports the results.
+        #[allow(dead_code)]
+        pub const RUN: advent_of_code::template::registry::RunFn = |input, is_timed| advent_of_code::solution!(@time input, is_timed $(, $parse)?);
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let puzzle = advent_of_code::template::puzzle_id(DAY, YEAR);
+            let input = advent_of_code::template::InputSource::from_args().read(puzzle);
+            $(let Some(input) = run_parse($parse, &input) else {
+                return;
+            };)?
+            run_part(part_one, &input, puzzle, 1);
+            run_part(part_two, &input, puzzle, 2);
+        }
+
+        /// The tests generated by `build.rs` from `data/<year>/examples`.
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn part_one(input: &str) -> impl advent_of_code::template::runner::PartOutput {
+                super::part_one(&advent_of_code::solution!(@parse input $(, $parse)?))
+            }
+
+            fn part_two(input: &str) -> impl advent_of_code::template::runner::PartOutput {
+                super::part_two(&advent_of_code::solution!(@parse input $(, $parse)?))
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/example_tests/", env!("CARGO_BIN_NAME"), ".rs"));
         }
     };
+    (@year) => {
+        advent_of_code::template::bin_year(env!("CARGO_BIN_NAME"))
+    };
+    (@year $year:expr) => {{
+        const _ASSERT: () = assert!(
+            advent_of_code::template::is_bin_year(env!("CARGO_BIN_NAME"), $year),
+            concat!("the year `", $year, "` of `solution!` is not the one of the file name"),
+        );
+        Some(advent_of_code::year!($year))
+    }};
+    (@time $input:ident, $is_timed:ident) => {
+        vec![
+            advent_of_code::template::runner::time_part(part_one, $input, 1, $is_timed),
+            advent_of_code::template::runner::time_part(part_two, $input, 2, $is_timed),
+        ]
+    };
+    (@time $input:ident, $is_timed:ident, $parse:path) => {
+        advent_of_code::template::runner::time_parsed($parse, part_one, part_two, $input, $is_timed)
+    };
+    (@parse $input:ident) => {
+        *$input
+    };
+    (@parse $input:ident, $parse:path) => {
+        $parse($input)
+    };
+    ($day:expr, year: $year:expr, parse: $parse:path) => {
+        advent_of_code::solution!(@impl $day, year: [$year], parse: [$parse]);
+    };
+    ($day:expr, year: $year:expr) => {
+        advent_of_code::solution!(@impl $day, year: [$year], parse: []);
+    };
+    ($day:expr, parse: $parse:path) => {
+        advent_of_code::solution!(@impl $day, year: [], parse: [$parse]);
+    };
+    ($day:expr) => {
+        advent_of_code::solution!(@impl $day, year: [], parse: []);
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_year, is_bin_year, latest_unlocked_day, InputSource};
+    use crate::{day, Year};
+    use std::time::Duration;
+
+    fn parse(args: &str) -> Option<InputSource> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        InputSource::parse(&args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse("01 --time"), Some(InputSource::Puzzle));
+        assert_eq!(parse("01 --input stress.txt --time"), Some(InputSource::File("stress.txt".into())));
+        assert_eq!(parse("01 --input -"), Some(InputSource::Stdin));
+        assert_eq!(parse("01 --example"), Some(InputSource::Example(1)));
+        assert_eq!(parse("01 --example --time"), Some(InputSource::Example(1)));
+        assert_eq!(parse("01 --example 2"), Some(InputSource::Example(2)));
+    }
+
+    #[test]
+    fn errors_on_invalid_input_sources() {
+        assert_eq!(parse("01 --input"), None);
+        assert_eq!(parse("01 --input --time"), None);
+        assert_eq!(parse("01 --example 0"), None);
+        assert_eq!(parse("01 --example 2 --input -"), None);
+    }
+
+    #[test]
+    fn input_sources_round_trip() {
+        for source in [InputSource::Puzzle, InputSource::File("a.txt".into()), InputSource::Stdin, InputSource::Example(3)] {
+            let args: Vec<String> = ["01".to_string()].into_iter().chain(source.to_args()).collect();
+            assert_eq!(InputSource::parse(&args), Some(source));
+        }
+    }
+
+    #[test]
+    fn reads_year_of_bin_names() {
+        assert_eq!(bin_year("2023_01"), Year::new(2023));
+        assert_eq!(bin_year("2015_25"), Year::new(2015));
+        assert_eq!(bin_year("advent_of_code"), None);
+        assert_eq!(bin_year("01"), None);
+        assert_eq!(bin_year("1999_01"), None);
+        assert_eq!(bin_year("20x3_01"), None);
+    }
+
+    #[test]
+    fn checks_year_of_bin_names() {
+        assert_eq!(is_bin_year("2022_08", 2022), true);
+        assert_eq!(is_bin_year("2023_08", 2022), false);
+        assert_eq!(is_bin_year("runner", 2022), true);
+    }
+
+    #[test]
+    fn finds_latest_unlocked_day() {
+        let year = Year::new(2023).unwrap();
+        let at = |secs: u64| latest_unlocked_day(year, Duration::from_secs(secs));
+        // 2023-12-01T04:59:59Z and 2023-12-01T05:00:00Z.
+        assert_eq!(at(1_701_406_799), None);
+        assert_eq!(at(1_701_406_800), Some(day!(1)));
+        // 2023-12-07T04:59:59Z and 2023-12-07T06:12:45Z.
+        assert_eq!(at(1_701_925_199), Some(day!(6)));
+        assert_eq!(at(1_701_929_565), Some(day!(7)));
+        // 2024-01-01T00:00:00Z.
+        assert_eq!(at(1_704_067_200), Some(day!(25)));
+    }
 }
@@ -0,0 +1,154 @@
+/// Machine-readable output of the solution binaries.
+/// When called with `--format json`, a solution binary prints one [`PartRecord`] per part and line
+/// instead of its human-readable output. This is what `all` consumes when running days in isolation.
+/// The runner prints the same records for each day it is asked to run, followed by [`END_OF_DAY`].
+use std::time::Duration;
+use std::{env, process};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::runner::{BenchStats, PartResult};
+
+/// Printed by the runner once it is done with a day, see [`registry::serve`](crate::template::registry::serve).
+pub const END_OF_DAY: &str = r#"{"end_of_day":true}"#;
+
+/// The output format of a solution binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    /// Parse the `--format` argument passed to a solution binary, defaults to [`OutputFormat::Text`].
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let Some(index) = args.iter().position(|x| x == "--format") else {
+            return Self::Text;
+        };
+
+        match args.get(index + 1).map(String::as_str) {
+            Some("text") => Self::Text,
+            Some("json") => Self::Json,
+            _ => {
+                eprintln!("Unexpected command-line input. Format: --format <text|json>");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// The result of one part of a solution, as exchanged between a solution binary and `all`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Median execution time over all samples.
+    pub duration_nanos: u64,
+    pub samples: u64,
+    pub mean_nanos: u64,
+    pub min_nanos: u64,
+    pub max_nanos: u64,
+    pub std_dev_nanos: u64,
+    pub p95_nanos: u64,
+    /// Set when the part failed to produce an answer.
+    pub error: Option<String>,
+}
+
+impl PartRecord {
+    /// Serializes the record to a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("part records are always serializable")
+    }
+
+    /// Parses a line of output as a record, returns [`None`] for any other output.
+    #[must_use]
+    pub fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn as_nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+impl From<&PartResult> for PartRecord {
+    fn from(result: &PartResult) -> Self {
+        let stats = &result.stats;
+
+        #[allow(clippy::cast_possible_truncation)]
+        Self {
+            part: result.part,
+            answer: result.answer.clone(),
+            duration_nanos: as_nanos(stats.median),
+            samples: stats.samples as u64,
+            mean_nanos: as_nanos(stats.mean),
+            min_nanos: as_nanos(stats.min),
+            max_nanos: as_nanos(stats.max),
+            std_dev_nanos: as_nanos(stats.std_dev),
+            p95_nanos: as_nanos(stats.p95),
+            error: result.error.clone(),
+        }
+    }
+}
+
+impl From<&PartRecord> for PartResult {
+    fn from(record: &PartRecord) -> Self {
+        Self {
+            part: record.part,
+            answer: record.answer.clone(),
+            error: record.error.clone(),
+            timed_out: false,
+            stats: BenchStats {
+                samples: u128::from(record.samples),
+                median: Duration::from_nanos(record.duration_nanos),
+                mean: Duration::from_nanos(record.mean_nanos),
+                min: Duration::from_nanos(record.min_nanos),
+                max: Duration::from_nanos(record.max_nanos),
+                std_dev: Duration::from_nanos(record.std_dev_nanos),
+                p95: Duration::from_nanos(record.p95_nanos),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartRecord, END_OF_DAY};
+    use crate::template::runner::{BenchStats, PartResult};
+    use std::time::Duration;
+
+    #[test]
+    fn round_trips_results() {
+        let result = PartResult {
+            part: 2,
+            answer: Some("multi\nline".into()),
+            error: None,
+            timed_out: false,
+            stats: BenchStats::from_samples(&[Duration::from_nanos(10), Duration::from_nanos(30)]),
+        };
+
+        let line = PartRecord::from(&result).to_json();
+        assert_eq!(line.contains('\n'), false);
+
+        let record = PartRecord::parse(&line).unwrap();
+        assert_eq!(record.duration_nanos, 20);
+        assert_eq!(record.samples, 2);
+        assert_eq!(PartResult::from(&record), result);
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartRecord::parse("[src/bin/08.rs:41:3] &solutions = ["), None);
+        assert_eq!(PartRecord::parse("{ not json"), None);
+        assert_eq!(PartRecord::parse(""), None);
+        assert_eq!(PartRecord::parse(END_OF_DAY), None);
+    }
+}
@@ -1,8 +1,11 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, time::Duration};
 
-use crate::Day;
+use serde::Deserialize;
+
+use crate::template::runner::BenchStats;
+use crate::{Day, PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,23 +24,84 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    /// Only set for solutions with a parse function.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
     pub total_nanos: f64,
+    /// Whether the answers matched the known-answer store, [`None`] if no answer is known.
+    pub passed: Option<bool>,
+}
+
+/// An optional column of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    /// The parse step, left out if no solution has one.
+    Parse,
+    /// The combined time of a day.
+    Total,
+    /// The share of a day in the total time.
+    Share,
+    /// The number of samples of each part.
+    Samples,
+    /// Whether the answers matched the known ones.
+    Answers,
+}
+
+/// The columns and tables of the benchmarks, set in the `[readme]` section of `data/<year>/config.toml`:
+///
+/// ```toml
+/// [readme]
+/// columns = ["parse", "total", "share", "samples", "answers"]
+/// # adds a second table with the days sorted by their time.
+/// sorted = true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sorted: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Parse],
+            sorted: false,
+        }
+    }
+}
+
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Total => "Total",
+            Column::Share => "Share",
+            Column::Samples => "Samples",
+            Column::Answers => "Answers",
+        }
+    }
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./src/bin/{}_{}.rs", id.year, id.day)
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the section between a pair of markers, which is replaced on each update. A single marker works as well,
+/// the section is inserted in its place.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,74 +122,152 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+fn table_row(cells: &[String]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+/// The table has a parse column only if at least one solution has a parse function.
+/// The columns after the parts are the ones of `options`, in the order of [`Column`]. `chart` is the path of a chart to link.
+fn construct_table(prefix: &str, year: Year, timings: Vec<Timings>, total_millis: f64, options: &TableOptions, chart: Option<&str>) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has = |column: Column| options.columns.contains(&column);
+    let has_parse = has(Column::Parse) && timings.iter().any(|t| t.parse.is_some());
+    let extra_columns: Vec<Column> = [Column::Total, Column::Share, Column::Samples, Column::Answers].into_iter().filter(|&c| has(c)).collect();
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    let sum_nanos: f64 = timings.iter().map(|t| t.total_nanos).sum();
+    let share = |timing: &Timings| {
+        let share = if sum_nanos > 0.0 { timing.total_nanos / sum_nanos * 100.0 } else { 0.0 };
+        format!("`{share:.1}%`")
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let total = |timing: &Timings| format!("`{:.1?}`", Duration::from_nanos(timing.total_nanos as u64));
+    let link = |timing: &Timings| format!("[Day {}]({})", timing.day.into_inner(), get_path_for_bin(PuzzleId::new(year, timing.day)));
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    columns.extend(extra_columns.iter().map(|c| c.title()));
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; columns.len()].join(" | ")));
+
+    for timing in &timings {
+        let or_dash = |x: &Option<String>| format!("`{}`", x.as_deref().unwrap_or("-"));
+
+        let mut cells = vec![link(timing)];
+        if has_parse {
+            cells.push(or_dash(&timing.parse));
+        }
+        cells.extend([or_dash(&timing.part_1), or_dash(&timing.part_2)]);
+
+        for column in &extra_columns {
+            cells.push(match column {
+                Column::Parse => unreachable!("the parse column comes before the parts"),
+                Column::Total => total(timing),
+                Column::Share => share(timing),
+                Column::Samples => [timing.part_1_stats, timing.part_2_stats]
+                    .iter()
+                    .map(|s| s.map_or_else(|| "-".into(), |s| format!("`{}`", s.samples)))
+                    .collect::<Vec<_>>()
+                    .join(" · "),
+                Column::Answers => match timing.passed {
+                    Some(true) => "✔".into(),
+                    Some(false) => "✖".into(),
+                    None => "-".into(),
+                },
+            });
+        }
+
+        lines.push(table_row(&cells));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if let Some(chart) = chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmarks chart](./{chart})"));
+    }
+
+    if options.sorted {
+        let mut sorted: Vec<&Timings> = timings.iter().collect();
+        sorted.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+
+        lines.push(String::new());
+        lines.push(format!("{prefix}# Where the time goes"));
+        lines.push(String::new());
+        lines.push("| Day | Total | Share |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+        for timing in sorted {
+            lines.push(table_row(&[link(timing), total(timing), share(timing)]));
+        }
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(s: &mut String, year: Year, timings: Vec<Timings>, total_millis: f64, options: &TableOptions, chart: Option<&str>) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, timings, total_millis, options, chart);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(year: Year, timings: Vec<Timings>, total_millis: f64, options: &TableOptions, chart: Option<&str>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis, options, chart)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{update_content, Column, TableOptions, Timings, MARKER};
+    use crate::template::runner::BenchStats;
+    use std::time::Duration;
+    use crate::{day, year};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
                 total_nanos: 3e+10,
+                passed: None,
             },
             Timings {
                 day: day!(2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
                 total_nanos: 7e+10,
+                passed: None,
             },
             Timings {
                 day: day!(4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
                 total_nanos: 9e+10,
+                passed: None,
             },
         ]
     }
@@ -134,28 +276,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &TableOptions::default(), None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &TableOptions::default(), None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &TableOptions::default(), None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &TableOptions::default(), None).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &TableOptions::default(), None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +305,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &TableOptions::default(), None).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -172,9 +314,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023_04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +325,74 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 190.0, &TableOptions::default(), None).unwrap();
+
+        assert_eq!(s.contains("| Day | Parse | Part 1 | Part 2 |"), true);
+        assert_eq!(s.contains("| [Day 1](./src/bin/2023_01.rs) | `-` | `10ms` | `20ms` |"), true);
+        assert_eq!(s.contains("| [Day 2](./src/bin/2023_02.rs) | `5ms` | `30ms` | `40ms` |"), true);
+    }
+
+    #[test]
+    fn format_benchmarks_with_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].passed = Some(true);
+        timings[1].passed = Some(false);
+        timings[2].part_1_stats = Some(BenchStats::from_samples(&[Duration::from_millis(40); 12]));
+
+        let options = TableOptions {
+            columns: vec![Column::Answers, Column::Total, Column::Share, Column::Samples],
+            sorted: false,
+        };
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 190.0, &options, None).unwrap();
+
+        assert_eq!(s.contains("| Day | Part 1 | Part 2 | Total | Share | Samples | Answers |"), true);
+        assert_eq!(s.contains("| :---: | :---: | :---: | :---: | :---: | :---: | :---:  |"), true);
+        assert_eq!(s.contains("| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` | `30.0s` | `15.8%` | - · - | ✔ |"), true);
+        assert_eq!(s.contains("| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` | `70.0s` | `36.8%` | - · - | ✖ |"), true);
+        assert_eq!(s.contains("| [Day 4](./src/bin/2023_04.rs) | `40ms` | `50ms` | `90.0s` | `47.4%` | `12` · - | - |"), true);
+        assert_eq!(s.contains("Where the time goes"), false);
+    }
+
+    #[test]
+    fn links_chart() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &TableOptions::default(), Some(".assets/benchmarks.svg")).unwrap();
+        assert_eq!(s.contains("**Total: 190.00ms**\n\n![Benchmarks chart](./.assets/benchmarks.svg)\n<!--- benchmarking table --->"), true);
+    }
+
+    #[test]
+    fn format_sorted_benchmarks() {
+        let options = TableOptions {
+            sorted: true,
+            ..TableOptions::default()
+        };
+        let mut s = format!("foo\n{}\n{}\nbar", MARKER, MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &options, None).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0, &options, None).unwrap();
+
+        let expected = [
+            "**Total: 190.00ms**",
+            "",
+            "### Where the time goes",
+            "",
+            "| Day | Total | Share |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/2023_04.rs) | `90.0s` | `47.4%` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `70.0s` | `36.8%` |",
+            "| [Day 1](./src/bin/2023_01.rs) | `30.0s` | `15.8%` |",
+            "<!--- benchmarking table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s.ends_with(&expected), true);
+        assert_eq!(s.matches(MARKER).count(), 2);
+    }
 }
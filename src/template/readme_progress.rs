@@ -0,0 +1,171 @@
+/// Module that updates the readme with the ⭐️ progress of a year, without the network or a session cookie.
+/// A part counts as solved when its answer is in the known-answer store, titles come from the downloaded puzzles.
+/// The table goes between its own pair of markers, the same way as the benchmarks.
+use std::{fs, path::Path};
+
+use crate::template::answers::{self, Answers};
+use crate::template::examples::get_path_for_puzzle;
+use crate::template::readme_benchmarks::{get_path_for_bin, locate_table, Error};
+use crate::{all_days, Day, PuzzleId, Year};
+
+static MARKER: &str = "<!--- progress table --->";
+
+/// The progress of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub day: Day,
+    pub title: Option<String>,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl Progress {
+    fn stars(&self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
+/// The progress of the days that are scaffolded or have a known answer.
+#[must_use]
+pub fn collect(year: Year) -> Vec<Progress> {
+    all_days()
+        .filter_map(|day| {
+            let id = PuzzleId::new(year, day);
+            let known = answers::load(id).unwrap_or_else(|e| {
+                eprintln!("Failed to load known answers of day {day}: {e}");
+                Answers::default()
+            });
+
+            if known.is_empty() && !Path::new(&get_path_for_bin(id)).exists() {
+                return None;
+            }
+
+            Some(Progress {
+                day,
+                title: fs::read_to_string(get_path_for_puzzle(id)).ok().and_then(|puzzle| parse_title(&puzzle)),
+                part_1: known.part(1).is_some(),
+                part_2: known.part(2).is_some(),
+            })
+        })
+        .collect()
+}
+
+/// The title of a puzzle from its first line, e.g. `\--- Day 1: Trebuchet?! ---`.
+fn parse_title(puzzle: &str) -> Option<String> {
+    let line = puzzle.lines().next()?.trim().trim_start_matches('\\');
+    let title = line.strip_prefix("--- Day")?.strip_suffix("---")?;
+    let (_, title) = title.split_once(':')?;
+    Some(title.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+fn construct_table(year: Year, progress: &[Progress]) -> String {
+    let stars: usize = progress.iter().map(Progress::stars).sum();
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Progress"),
+        String::new(),
+        "| Day | Title | Part 1 | Part 2 |".into(),
+        "| :---: | :--- | :---: | :---: |".into(),
+    ];
+
+    for p in progress {
+        let star = |solved: bool| if solved { "⭐" } else { " " };
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} | {} |",
+            p.day.into_inner(),
+            p.day.into_inner(),
+            p.title.as_deref().map_or_else(|| "-".into(), |t| t.replace('|', "\\|")),
+            star(p.part_1),
+            star(p.part_2)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**⭐ {stars} / {}**", all_days().count() * 2));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: Year, progress: &[Progress]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(year, progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Writes the progress table to the readme, returns the number of stars.
+pub fn update(year: Year) -> Result<usize, Error> {
+    let path = "README.md";
+    let progress = collect(year);
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, &progress)?;
+    fs::write(path, &readme)?;
+    Ok(progress.iter().map(Progress::stars).sum())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, update_content, Progress, MARKER};
+    use crate::{day, Year};
+
+    fn get_mock_progress() -> Vec<Progress> {
+        vec![
+            Progress {
+                day: day!(1),
+                title: Some("Trebuchet?!".into()),
+                part_1: true,
+                part_2: true,
+            },
+            Progress {
+                day: day!(2),
+                title: None,
+                part_1: true,
+                part_2: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(parse_title("\\--- Day 1: Trebuchet?! ---\n----------\n"), Some("Trebuchet?!".into()));
+        assert_eq!(parse_title("--- Day 12: Hot Springs ---"), Some("Hot Springs".into()));
+        assert_eq!(parse_title("# something else"), None);
+        assert_eq!(parse_title(""), None);
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\n{}\nbar", MARKER, MARKER);
+        update_content(&mut s, Year::new(2023).unwrap(), &get_mock_progress()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- progress table --->",
+            "## 2023 Progress",
+            "",
+            "| Day | Title | Part 1 | Part 2 |",
+            "| :---: | :--- | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | Trebuchet?! | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | - | ⭐ |   |",
+            "",
+            "**⭐ 3 / 50**",
+            "<!--- progress table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_single_marker_and_keeps_benchmarks() {
+        let benchmarks = "<!--- benchmarking table --->";
+        let mut s = format!("{MARKER}\n\n{benchmarks}\n");
+        update_content(&mut s, Year::new(2023).unwrap(), &get_mock_progress()).unwrap();
+        update_content(&mut s, Year::new(2023).unwrap(), &get_mock_progress()).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2023 Progress").count(), 1);
+        assert_eq!(s.ends_with(&format!("{MARKER}\n\n{benchmarks}\n")), true);
+    }
+}
@@ -0,0 +1,93 @@
+/// In-process registry of the solutions.
+/// Every `src/bin/<year>_<day>.rs` exposes its parts through the [`solution!`](crate::solution) macro and
+/// `build.rs` registers them for the puzzle of the file in the runner, a binary of its own (`src/runner.rs`).
+/// The runner lets `all` and `verify` run every day in a single process instead of spawning one per day,
+/// while the main binary does not depend on the solutions, so that a day that does not compile only breaks the runner.
+use std::{
+    env, fs,
+    io::{self, BufRead, Write},
+    panic,
+};
+
+use crate::template::commands::all::get_path_for_input;
+use crate::template::protocol::{PartRecord, END_OF_DAY};
+use crate::template::runner::PartResult;
+use crate::PuzzleId;
+
+/// Runs the parts of a solution against an input without printing them, benching them if the flag is set.
+/// Solutions with a parse function also return its timing first, see [`PARSE_STEP`](crate::template::runner::PARSE_STEP).
+pub type RunFn = fn(&str, bool) -> Vec<PartResult>;
+
+/// The parts of a day's solution.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub id: PuzzleId,
+    pub run: RunFn,
+}
+
+impl Solution {
+    /// Runs both parts of the solution against `input`.
+    pub fn run(&self, input: &str, is_timed: bool) -> Vec<PartResult> {
+        (self.run)(input, is_timed)
+    }
+}
+
+/// Finds the solution registered for a given puzzle.
+#[must_use]
+pub fn find(solutions: &[Solution], id: PuzzleId) -> Option<&Solution> {
+    solutions.iter().find(|s| s.id == id)
+}
+
+/// The main function of the runner. Reads one puzzle per line of stdin, e.g. `2023/07`, runs its solution on the puzzle input
+/// and prints a [`PartRecord`] per part, followed by [`END_OF_DAY`]. The parts are benched when the runner is called with `--time`.
+pub fn serve(solutions: &[Solution]) {
+    let is_timed = env::args().any(|x| x == "--time");
+
+    for line in io::stdin().lock().lines().map_while(Result::ok) {
+        let results = match parse_id(&line) {
+            Some(id) => run(solutions, id, is_timed),
+            None => failed(&format!("\"{line}\" is not a puzzle")),
+        };
+
+        for result in &results {
+            println!("{}", PartRecord::from(result).to_json());
+        }
+        println!("{END_OF_DAY}");
+        let _ = io::stdout().flush();
+    }
+}
+
+fn run(solutions: &[Solution], id: PuzzleId, is_timed: bool) -> Vec<PartResult> {
+    let Some(solution) = find(solutions, id) else {
+        return failed("the solution is not compiled into the runner");
+    };
+
+    match fs::read_to_string(get_path_for_input(id)) {
+        // panics of the parts are caught by the runner, this only guards against the rest of the solution.
+        Ok(input) => panic::catch_unwind(|| solution.run(&input, is_timed)).unwrap_or_default(),
+        Err(e) => failed(&format!("could not read input file: {e}")),
+    }
+}
+
+fn failed(error: &str) -> Vec<PartResult> {
+    vec![PartResult::failed(1, error.into()), PartResult::failed(2, error.into())]
+}
+
+fn parse_id(s: &str) -> Option<PuzzleId> {
+    let (year, day) = s.trim().split_once('/')?;
+    Some(PuzzleId::new(year.parse().ok()?, day.parse().ok()?))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_id;
+    use crate::{day, year, PuzzleId};
+
+    #[test]
+    fn parses_puzzles() {
+        let id = PuzzleId::new(year!(2023), day!(7));
+        assert_eq!(parse_id(&id.to_string()), Some(id));
+        assert_eq!(parse_id("2023/26"), None);
+        assert_eq!(parse_id("2023"), None);
+    }
+}
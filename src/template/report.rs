@@ -0,0 +1,397 @@
+/// Module that writes machine-readable reports of solution runs for CI.
+/// Every part that ran produces one [`ReportEntry`], rendered as JSON, CSV or JUnit XML.
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{error, fmt, fs, io};
+
+use serde::Serialize;
+
+use crate::template::runner::PartResult;
+use crate::Day;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Junit,
+}
+
+impl ReportFormat {
+    /// The file a report is written to when no `--out` path is given.
+    #[must_use]
+    pub fn default_path(self) -> PathBuf {
+        match self {
+            ReportFormat::Json => "report.json".into(),
+            ReportFormat::Csv => "report.csv".into(),
+            ReportFormat::Junit => "report.xml".into(),
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = ReportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            "junit" => Ok(ReportFormat::Junit),
+            _ => Err(ReportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ReportFormat`].
+#[derive(Debug)]
+pub struct ReportFormatFromStrError;
+
+impl error::Error for ReportFormatFromStrError {}
+
+impl Display for ReportFormatFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expecting a report format of json, csv or junit")
+    }
+}
+
+/// Where and how to write a report, as passed with `--report <format> --out <path>`.
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    pub format: ReportFormat,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The answer matches the known answer.
+    Passed,
+    /// The answer differs from the known answer.
+    Failed,
+    /// An answer was produced but there is no known answer to check it against.
+    Unverified,
+    /// The part did not produce an answer.
+    Unsolved,
+    /// The part returned an error.
+    Errored,
+    /// The part was stopped by a timeout.
+    TimedOut,
+}
+
+impl Status {
+    #[must_use]
+    pub fn new(answer: Option<&str>, expected: Option<&str>) -> Self {
+        match (answer, expected) {
+            (None, _) => Status::Unsolved,
+            (Some(_), None) => Status::Unverified,
+            (Some(answer), Some(expected)) if answer == expected => Status::Passed,
+            (Some(_), Some(_)) => Status::Failed,
+        }
+    }
+
+    /// The status of a part that ran, [`Status::Errored`] if it returned an error.
+    #[must_use]
+    pub fn of(result: &PartResult, expected: Option<&str>) -> Self {
+        if result.timed_out {
+            Status::TimedOut
+        } else if result.error.is_some() {
+            Status::Errored
+        } else {
+            Status::new(result.answer.as_deref(), expected)
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Passed => "passed",
+            Status::Failed => "failed",
+            Status::Unverified => "unverified",
+            Status::Unsolved => "unsolved",
+            Status::Errored => "errored",
+            Status::TimedOut => "timed_out",
+        })
+    }
+}
+
+/// The outcome of one part of one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportEntry {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    pub duration_nanos: u64,
+    pub samples: u64,
+}
+
+impl ReportEntry {
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(day: Day, result: &PartResult, expected: Option<String>) -> Self {
+        Self {
+            day: day.into_inner(),
+            part: result.part,
+            status: Status::of(result, expected.as_deref()),
+            answer: result.answer.clone(),
+            expected,
+            error: result.error.clone(),
+            duration_nanos: result.stats.median.as_nanos() as u64,
+            samples: result.stats.samples as u64,
+        }
+    }
+}
+
+#[must_use]
+pub fn render(format: ReportFormat, entries: &[ReportEntry]) -> String {
+    match format {
+        ReportFormat::Json => render_json(entries),
+        ReportFormat::Csv => render_csv(entries),
+        ReportFormat::Junit => render_junit(entries),
+    }
+}
+
+pub fn write(options: &ReportOptions, entries: &[ReportEntry]) -> Result<(), io::Error> {
+    write_to(&options.path, options.format, entries)
+}
+
+fn write_to(path: &Path, format: ReportFormat, entries: &[ReportEntry]) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    fs::write(path, render(format, entries))
+}
+
+fn render_json(entries: &[ReportEntry]) -> String {
+    serde_json::to_string_pretty(entries).expect("report entries are always serializable")
+}
+
+fn render_csv(entries: &[ReportEntry]) -> String {
+    let mut lines = vec!["day,part,status,answer,expected,duration_nanos,samples,error".to_string()];
+
+    for entry in entries {
+        lines.push(format!(
+            "{},{},{},{},{},{},{},{}",
+            entry.day,
+            entry.part,
+            entry.status,
+            escape_csv(entry.answer.as_deref().unwrap_or_default()),
+            escape_csv(entry.expected.as_deref().unwrap_or_default()),
+            entry.duration_nanos,
+            entry.samples,
+            escape_csv(entry.error.as_deref().unwrap_or_default())
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn seconds(nanos: u64) -> String {
+    format!("{:.6}", nanos as f64 / 1e9)
+}
+
+fn render_junit(entries: &[ReportEntry]) -> String {
+    let count = |status: Status| entries.iter().filter(|e| e.status == status).count();
+
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!(
+            r#"<testsuites name="advent_of_code" tests="{}" failures="{}" errors="{}" skipped="{}">"#,
+            entries.len(),
+            count(Status::Failed),
+            count(Status::Errored) + count(Status::TimedOut),
+            count(Status::Unsolved)
+        ),
+    ];
+
+    let mut days: Vec<u8> = entries.iter().map(|e| e.day).collect();
+    days.dedup();
+
+    for day in days {
+        let day_entries: Vec<&ReportEntry> = entries.iter().filter(|e| e.day == day).collect();
+        let day_count = |status: Status| day_entries.iter().filter(|e| e.status == status).count();
+
+        lines.push(format!(
+            r#"  <testsuite name="Day {day:02}" tests="{}" failures="{}" errors="{}" skipped="{}" time="{}">"#,
+            day_entries.len(),
+            day_count(Status::Failed),
+            day_count(Status::Errored) + day_count(Status::TimedOut),
+            day_count(Status::Unsolved),
+            seconds(day_entries.iter().map(|e| e.duration_nanos).sum())
+        ));
+
+        for entry in day_entries {
+            let open = format!(r#"    <testcase classname="day_{day:02}" name="part_{}" time="{}""#, entry.part, seconds(entry.duration_nanos));
+
+            match entry.status {
+                Status::Passed | Status::Unverified => lines.push(format!("{open}/>")),
+                Status::Failed => {
+                    lines.push(format!("{open}>"));
+                    lines.push(format!(
+                        r#"      <failure message="expected {}, got {}"/>"#,
+                        escape_xml(entry.expected.as_deref().unwrap_or_default()),
+                        escape_xml(entry.answer.as_deref().unwrap_or_default())
+                    ));
+                    lines.push("    </testcase>".into());
+                }
+                Status::Unsolved => {
+                    lines.push(format!("{open}>"));
+                    lines.push(r#"      <skipped message="not solved"/>"#.into());
+                    lines.push("    </testcase>".into());
+                }
+                Status::Errored | Status::TimedOut => {
+                    let kind = if entry.status == Status::TimedOut { "timeout" } else { "error" };
+                    lines.push(format!("{open}>"));
+                    lines.push(format!(
+                        r#"      <error type="{kind}" message="{}"/>"#,
+                        escape_xml(entry.error.as_deref().unwrap_or_default())
+                    ));
+                    lines.push("    </testcase>".into());
+                }
+            }
+        }
+
+        lines.push("  </testsuite>".into());
+    }
+
+    lines.push("</testsuites>".into());
+    lines.join("\n") + "\n"
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, ReportEntry, ReportFormat, Status};
+    use crate::template::runner::PartResult;
+    use std::time::Duration;
+
+    fn get_mock_entries() -> Vec<ReportEntry> {
+        vec![
+            ReportEntry {
+                day: 1,
+                part: 1,
+                status: Status::Passed,
+                answer: Some("42".into()),
+                expected: Some("42".into()),
+                error: None,
+                duration_nanos: 1000,
+                samples: 10,
+            },
+            ReportEntry {
+                day: 1,
+                part: 2,
+                status: Status::Failed,
+                answer: Some("a,\"b\"".into()),
+                expected: Some("<c>".into()),
+                error: None,
+                duration_nanos: 2_000_000,
+                samples: 1,
+            },
+            ReportEntry {
+                day: 3,
+                part: 1,
+                status: Status::Unsolved,
+                answer: None,
+                expected: None,
+                error: None,
+                duration_nanos: 10,
+                samples: 1,
+            },
+            ReportEntry {
+                day: 3,
+                part: 2,
+                status: Status::Errored,
+                answer: None,
+                expected: Some("7".into()),
+                error: Some("invalid hand: 32T3".into()),
+                duration_nanos: 20,
+                samples: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn status_from_answers() {
+        assert_eq!(Status::new(None, Some("1")), Status::Unsolved);
+        assert_eq!(Status::new(Some("1"), None), Status::Unverified);
+        assert_eq!(Status::new(Some("1"), Some("1")), Status::Passed);
+        assert_eq!(Status::new(Some("1"), Some("2")), Status::Failed);
+    }
+
+    #[test]
+    fn status_of_timed_out_parts() {
+        let result = PartResult::timed_out(2, Duration::from_secs(10));
+        assert_eq!(Status::of(&result, Some("1")), Status::TimedOut);
+        assert_eq!(result.error.as_deref(), Some("timed out after 10s"));
+    }
+
+    #[test]
+    fn format_json() {
+        let json = render(ReportFormat::Json, &get_mock_entries());
+        assert_eq!(json.contains(r#""status": "failed""#), true);
+        assert_eq!(json.contains(r#""duration_nanos": 1000"#), true);
+    }
+
+    #[test]
+    fn format_csv() {
+        let csv = render(ReportFormat::Csv, &get_mock_entries());
+        let expected = [
+            "day,part,status,answer,expected,duration_nanos,samples,error",
+            "1,1,passed,42,42,1000,10,",
+            "1,2,failed,\"a,\"\"b\"\"\",<c>,2000000,1,",
+            "3,1,unsolved,,,10,1,",
+            "3,2,errored,,7,20,1,invalid hand: 32T3",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn format_junit() {
+        let xml = render(ReportFormat::Junit, &get_mock_entries());
+        let expected = [
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<testsuites name="advent_of_code" tests="4" failures="1" errors="1" skipped="1">"#,
+            r#"  <testsuite name="Day 01" tests="2" failures="1" errors="0" skipped="0" time="0.002001">"#,
+            r#"    <testcase classname="day_01" name="part_1" time="0.000001"/>"#,
+            r#"    <testcase classname="day_01" name="part_2" time="0.002000">"#,
+            r#"      <failure message="expected &lt;c&gt;, got a,&quot;b&quot;"/>"#,
+            r#"    </testcase>"#,
+            r#"  </testsuite>"#,
+            r#"  <testsuite name="Day 03" tests="2" failures="0" errors="1" skipped="1" time="0.000000">"#,
+            r#"    <testcase classname="day_03" name="part_1" time="0.000000">"#,
+            r#"      <skipped message="not solved"/>"#,
+            r#"    </testcase>"#,
+            r#"    <testcase classname="day_03" name="part_2" time="0.000000">"#,
+            r#"      <error type="error" message="invalid hand: 32T3"/>"#,
+            r#"    </testcase>"#,
+            r#"  </testsuite>"#,
+            r#"</testsuites>"#,
+            "",
+        ]
+        .join("\n");
+        assert_eq!(xml, expected);
+    }
+}
@@ -1,63 +1,398 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::protocol::{OutputFormat, PartRecord};
+use crate::template::submissions::{self, Response};
+use crate::template::{answers, aoc_client, InputSource, ANSI_ITALIC, ANSI_RESET};
+use crate::PuzzleId;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// What a solution part returns: an [`Option`], or a [`Result`] whose error is shown instead of the answer,
+/// e.g. an [`anyhow::Result`].
+pub trait PartOutput {
+    /// The answer as text, [`None`] if the part is not solved, or the error chain if the part failed.
+    ///
+    /// # Errors
+    /// When the part returned an error.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        // the alternate form of an `anyhow::Error` includes its causes, e.g. "invalid hand: missing card".
+        self.as_ref().map(|x| Some(x.to_string())).map_err(|e| format!("{e:#}"))
+    }
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(func: impl Fn(I) -> R, input: I, id: PuzzleId, part: u8) {
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let result = match OutputFormat::from_args() {
+        OutputFormat::Text => measure_part(func, input, part, is_timed),
+        OutputFormat::Json => {
+            let result = time_part(func, input, part, is_timed);
+            println!("{}", PartRecord::from(&result).to_json());
+            result
+        }
+    };
+
+    if let Some(answer) = result.answer {
+        submit_result(answer, id, part);
+    }
+}
+
+/// Run the parse function of a solution, reporting its timing like a part. Returns the parsed input,
+/// or [`None`] after reporting both parts as failed if the parse function panicked.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str) -> Option<P> {
+    let is_timed = env::args().any(|x| x == "--time");
+    let format = OutputFormat::from_args();
+
+    let (parsed, result) = match format {
+        OutputFormat::Text => measure_parse(func, input, is_timed),
+        OutputFormat::Json => time_parse(func, input, is_timed),
+    };
+
+    let mut results = vec![result];
+    if parsed.is_none() {
+        results.extend(unparsed_parts());
+    }
+
+    for result in &results {
+        match format {
+            OutputFormat::Text if result.part != PARSE_STEP => print_part_result(result),
+            OutputFormat::Text => {}
+            OutputFormat::Json => println!("{}", PartRecord::from(result).to_json()),
+        }
+    }
+
+    parsed
+}
+
+/// Run the parse function of a solution and print its timing, benching it if `is_timed` is set.
+pub fn measure_parse<P>(func: impl Fn(&str) -> P, input: &str, is_timed: bool) -> (Option<P>, PartResult) {
+    let (parsed, stats) = run_timed(func, input, is_timed, |_| {
+        if is_timed {
+            print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+    });
+
+    let (parsed, result) = parse_result(parsed, stats);
+    print_part_result(&result);
+    (parsed, result)
+}
+
+/// Run the parse function of a solution without printing anything, benching it if `is_timed` is set.
+pub fn time_parse<P>(func: impl Fn(&str) -> P, input: &str, is_timed: bool) -> (Option<P>, PartResult) {
+    let (parsed, stats) = run_timed(func, input, is_timed, |_| {});
+    parse_result(parsed, stats)
+}
+
+/// Run the parse function and both parts of a solution without printing anything. See [`time_part`].
+pub fn time_parsed<P, R1: PartOutput, R2: PartOutput>(
+    parse: impl Fn(&str) -> P,
+    part_one: impl Fn(&P) -> R1,
+    part_two: impl Fn(&P) -> R2,
+    input: &str,
+    is_timed: bool,
+) -> Vec<PartResult> {
+    let (parsed, result) = time_parse(parse, input, is_timed);
+
+    let mut results = vec![result];
+    match parsed {
+        Some(parsed) => {
+            results.push(time_part(part_one, &parsed, 1, is_timed));
+            results.push(time_part(part_two, &parsed, 2, is_timed));
+        }
+        None => results.extend(unparsed_parts()),
+    }
+
+    results
+}
+
+fn parse_result<P>(parsed: Result<P, String>, stats: BenchStats) -> (Option<P>, PartResult) {
+    let (parsed, error) = match parsed {
+        Ok(parsed) => (Some(parsed), None),
+        Err(error) => (None, Some(error)),
+    };
+
+    let result = PartResult {
+        part: PARSE_STEP,
+        answer: None,
+        error,
+        timed_out: false,
+        stats,
+    };
+
+    (parsed, result)
+}
+
+/// The results of both parts when the parse function failed, they do not run.
+fn unparsed_parts() -> [PartResult; 2] {
+    [1, 2].map(|part| PartResult {
+        part,
+        answer: None,
+        error: Some("the input could not be parsed".into()),
+        timed_out: false,
+        stats: BenchStats::from_samples(&[Duration::ZERO]),
+    })
+}
+
+/// Run a solution part and print its result, benching it if `is_timed` is set.
+/// A panic of the part is caught and reported as its error.
+pub fn measure_part<I: Clone, R: PartOutput>(func: impl Fn(I) -> R, input: I, part: u8, is_timed: bool) -> PartResult {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(func, input, is_timed, |result| {
+        print_intermediate_result(&result.to_answer().unwrap_or_default(), &part_str);
+        if is_timed {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+    });
+
+    let result = PartResult::new(part, result, stats);
+    print_part_result(&result);
+    result
+}
+
+/// Run a solution part without printing anything, benching it if `is_timed` is set.
+pub fn time_part<I: Clone, R: PartOutput>(func: impl Fn(I) -> R, input: I, part: u8, is_timed: bool) -> PartResult {
+    let (result, stats) = run_timed(func, input, is_timed, |_| {});
+    PartResult::new(part, result, stats)
+}
+
+/// Print the final result line of a solution part, followed by its statistics if it was benched.
+pub fn print_part_result(result: &PartResult) {
+    // overwrite the intermediate result printed while benching.
+    print!("\r{}", format_part_result(result));
+}
+
+/// Format the final result line of a solution part as [`print_part_result`] prints it, e.g. to buffer it.
+#[must_use]
+pub fn format_part_result(result: &PartResult) -> String {
+    if let Some(error) = &result.error {
+        let label = if result.part == PARSE_STEP { "Parse".to_string() } else { format!("Part {}", result.part) };
+        if result.timed_out {
+            return format!("{label}: ✖ {error}\n");
+        }
+        return format!("{label}: ✖ {ANSI_ITALIC}error:{ANSI_RESET} {error}\n");
+    }
+
+    let mut str = if result.part == PARSE_STEP {
+        format!("Parse:{}             \n", format_duration(&result.stats))
+    } else {
+        let part_str = format!("Part {}", result.part);
+        format_result(&result.answer, &part_str, &format_duration(&result.stats))
+    };
+
+    if result.stats.samples > 1 {
+        str.push_str(&format_stats(&result.stats));
+        str.push('\n');
+    }
+
+    str
+}
+
+/// The `part` of the result that times the parse function of a solution, see [`solution!`](crate::solution).
+/// It never has an answer.
+pub const PARSE_STEP: u8 = 0;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// The outcome of running one part of a solution, or its parse function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Set when the part returned an error instead of an answer.
+    pub error: Option<String>,
+    /// Set when the part was stopped by a timeout, [`PartResult::error`] describes it.
+    pub timed_out: bool,
+    pub stats: BenchStats,
+}
+
+impl PartResult {
+    /// The result of a part from its output, or from the message of its panic.
+    #[must_use]
+    pub fn new(part: u8, output: Result<impl PartOutput, String>, stats: BenchStats) -> Self {
+        let (answer, error) = match output.and_then(|output| output.to_answer()) {
+            Ok(answer) => (answer, None),
+            Err(error) => (None, Some(error)),
+        };
+
+        Self {
+            part,
+            answer,
+            error,
+            timed_out: false,
+            stats,
+        }
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    /// The result of a part that never ran, e.g. because its day did not compile.
+    #[must_use]
+    pub fn failed(part: u8, error: String) -> Self {
+        Self {
+            part,
+            answer: None,
+            error: Some(error),
+            timed_out: false,
+            stats: BenchStats::from_samples(&[Duration::ZERO]),
+        }
+    }
+
+    /// The result of a part that did not finish before the deadline, see `--timeout`.
+    #[must_use]
+    pub fn timed_out(part: u8, timeout: Duration) -> Self {
+        Self {
+            part,
+            answer: None,
+            error: Some(format!("timed out after {}", format_timeout(timeout))),
+            timed_out: true,
+            stats: BenchStats::from_samples(&[timeout]),
+        }
+    }
+}
+
+/// Summary statistics of the samples collected while timing a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub median: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+}
+
+impl BenchStats {
+    /// Computes the statistics of a non-empty set of samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let len = sorted.len();
+        let mean_nanos = average_duration(&sorted);
+
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|x| (x.as_nanos() as f64 - mean_nanos as f64).powi(2))
+            .sum::<f64>()
+            / len as f64;
+
+        // nearest-rank percentile.
+        let p95_rank = (len * 95).div_ceil(100);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            samples: len as u128,
+            median,
+            mean: Duration::from_nanos(mean_nanos as u64),
+            min: sorted[0],
+            max: sorted[len - 1],
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            p95: sorted[cmp::max(p95_rank, 1) - 1],
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///
+/// A panic of the function is caught and returned as an error, see [`catch_panic`].
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, is_timed: bool, hook: impl Fn(&T)) -> (Result<T, String>, BenchStats) {
     let timer = Instant::now();
-    let result = func(input.clone());
+    let result = catch_panic(|| func(input.clone()));
     let base_time = timer.elapsed();
 
+    let single_sample = BenchStats::from_samples(&[base_time]);
+    let Ok(result) = result else {
+        return (result, single_sample);
+    };
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    if !is_timed {
+        return (Ok(result), single_sample);
+    }
+
+    match catch_panic(|| bench(func, input, &base_time)) {
+        Ok(stats) => (Ok(result), stats),
+        Err(error) => (Err(error), single_sample),
+    }
+}
 
-    (result, run.0, run.1)
+thread_local! {
+    /// Set while a part runs on this thread, so that the panic hook records its panics instead of printing them.
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// The message and location of the last panic recorded by the panic hook on this thread.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+static PANIC_HOOK: Once = Once::new();
+
+/// Runs `func`, turning a panic into an error with its message and location, e.g.
+/// `panicked at src/bin/07.rs:110:5: Invalid hand`. Panics of other threads (e.g. rayon workers)
+/// only carry their message, their location is printed by the default hook.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !IS_CATCHING.get() {
+                default_hook(info);
+                return;
+            }
+            let location = info.location().map(|l| format!(" at {}:{}:{}", l.file(), l.line(), l.column())).unwrap_or_default();
+            LAST_PANIC.set(Some(format!("panicked{location}: {}", panic_message(info.payload()))));
+        }));
+    });
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let was_catching = IS_CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.set(was_catching);
+
+    result.map_err(|payload| LAST_PANIC.take().unwrap_or_else(|| format!("panicked: {}", panic_message(&*payload))))
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+/// Bench a solution part. A few warmup iterations are executed and thrown away first
+/// so that one-off costs (page faults, thread pool spin-up, ...) do not end up in the samples.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,11 +404,7 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -84,57 +415,49 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+/// Formats a timeout in whole seconds, e.g. `10s`.
+#[must_use]
+pub fn format_timeout(timeout: Duration) -> String {
+    format!("{}s", timeout.as_secs())
+}
+
+fn format_duration(stats: &BenchStats) -> String {
+    let duration = stats.median;
+    if stats.samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} @ {} samples)", stats.samples)
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+/// Formats the detailed statistics of a benched part, printed on the line following its result.
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "  ↳ mean {:.1?} · min {:.1?} · max {:.1?} · σ {:.1?} · p95 {:.1?}",
+        stats.mean, stats.min, stats.max, stats.std_dev, stats.p95
+    )
+}
 
+/// Print the result of a solution part before it is benched, the final result line overwrites it.
+fn print_intermediate_result(result: &Option<String>, part: &str) {
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
+        Some(result) if result.contains('\n') => print!("{part}: ▼ "),
+        Some(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        None => print!("{part}: ✖"),
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+fn format_result(result: &Option<String>, part: &str, duration_str: &str) -> String {
+    match result {
+        Some(result) if result.contains('\n') => format!("{part}: ▼ {duration_str}\n{result}\n"),
+        Some(result) => format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}\n"),
+        None => format!("{part}: ✖             \n"),
+    }
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution.
+/// Answers refused by the submission history are not sent, an accepted answer is saved to the known-answer store.
+fn submit_result<T: Display>(result: T, id: PuzzleId, part: u8) -> Option<Result<String, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +480,103 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = result.to_string();
+
+    if InputSource::from_args() != InputSource::Puzzle {
+        eprintln!("Refusing to submit {answer}: it was not computed on the puzzle input.");
+        return None;
+    }
+
+    let mut history = submissions::load(id).unwrap_or_else(|e| {
+        eprintln!("Failed to load previous submissions: {e}");
         process::exit(1);
+    });
+
+    if let Err(refusal) = history.part_mut(part).check(&answer, submissions::now()) {
+        eprintln!("Refusing to submit {answer}: {refusal}");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    let output = aoc_client::submit(id, part, &answer);
+
+    let response = match &output {
+        Ok(text) => Response::parse(text),
+        Err(e) => {
+            eprintln!("Failed to submit: {e}");
+            Response::Unknown
+        }
+    };
+
+    history.part_mut(part).record(&answer, response, submissions::now());
+    if let Err(e) = submissions::save(id, &history) {
+        eprintln!("Failed to save submission: {e}");
+    }
+
+    if response == Response::Correct {
+        match answers::record(id, part, &answer) {
+            Ok(()) => println!("Saved answer to \"{}\".", answers::get_path_for_answers(id)),
+            Err(e) => eprintln!("Failed to save answer: {e}"),
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{catch_panic, time_part, BenchStats, PartOutput};
+    use std::time::Duration;
+
+    #[test]
+    fn catches_panics() {
+        let result = catch_panic(|| -> u32 { panic!("Invalid hand: {}", "32T3") });
+        let error = result.unwrap_err();
+        assert_eq!(error.starts_with("panicked at src/template/runner.rs:"), true);
+        assert_eq!(error.ends_with(": Invalid hand: 32T3"), true);
+
+        assert_eq!(catch_panic(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn panicking_part_fails() {
+        let result = time_part(|_: &str| -> Option<u32> { panic!("missing node id") }, "AAA", 1, false);
+        assert_eq!(result.answer, None);
+        assert_eq!(result.error.unwrap().ends_with(": missing node id"), true);
+    }
+
+    #[test]
+    fn answers_from_outputs() {
+        assert_eq!(Some(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.to_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>("abc").to_answer(), Ok(Some("abc".into())));
+
+        let error = anyhow::anyhow!("missing card").context("invalid hand");
+        assert_eq!(Err::<u32, _>(error).to_answer(), Err("invalid hand: missing card".into()));
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let samples: Vec<Duration> = [5, 1, 4, 2, 3, 100].into_iter().map(Duration::from_nanos).collect();
+        let stats = BenchStats::from_samples(&samples);
+
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.median, Duration::from_nanos(3) + Duration::from_nanos(1) / 2);
+        assert_eq!(stats.mean, Duration::from_nanos(19));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.max, Duration::from_nanos(100));
+        assert_eq!(stats.std_dev, Duration::from_nanos(36));
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn stats_from_single_sample() {
+        let stats = BenchStats::from_samples(&[Duration::from_micros(7)]);
+
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, Duration::from_micros(7));
+        assert_eq!(stats.min, stats.max);
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.p95, Duration::from_micros(7));
+    }
 }
@@ -0,0 +1,343 @@
+/// Module that keeps track of the answers submitted for each part in `data/<year>/submissions/NN.toml`.
+/// Submissions that are known to be wrong, that fall outside of the bounds learned from previous
+/// "too high" / "too low" responses, or that are sent before the cooldown expired are refused locally.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::PuzzleId;
+
+use super::get_data_dir;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse submissions: {e}"),
+            Error::IO(e) => write!(f, "could not access submissions file: {e}"),
+        }
+    }
+}
+
+/// The response of the Advent of Code website to a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    TooHigh { wait_secs: Option<u64> },
+    TooLow { wait_secs: Option<u64> },
+    Wrong { wait_secs: Option<u64> },
+    /// The answer was submitted too recently, nothing was checked.
+    TooSoon { wait_secs: u64 },
+    /// The part was already solved, or the response could not be understood.
+    Unknown,
+}
+
+impl Response {
+    /// Parses the text of a submission response, see [`aoc_client::submit`](crate::template::aoc_client::submit).
+    #[must_use]
+    pub fn parse(s: &str) -> Self {
+        let s = s.replace('\n', " ");
+
+        if s.contains("That's the right answer") {
+            return Response::Correct;
+        }
+
+        if s.contains("You gave an answer too recently") {
+            let wait_secs = s
+                .split("You have ")
+                .nth(1)
+                .and_then(|x| x.split(" left to wait").next())
+                .and_then(parse_wait)
+                .unwrap_or(60);
+            return Response::TooSoon { wait_secs };
+        }
+
+        if s.contains("That's not the right answer") {
+            // the site asks to "Please wait one minute", or to "please wait 5 minutes" after several wrong answers.
+            let lowercase = s.to_ascii_lowercase();
+            let wait_secs = lowercase.split("please wait ").nth(1).and_then(|x| x.split(" before trying again").next()).and_then(parse_wait);
+
+            return if s.contains("your answer is too high") {
+                Response::TooHigh { wait_secs }
+            } else if s.contains("your answer is too low") {
+                Response::TooLow { wait_secs }
+            } else {
+                Response::Wrong { wait_secs }
+            };
+        }
+
+        Response::Unknown
+    }
+}
+
+/// Parses durations such as `39s`, `1m 4s`, `one minute` or `5 minutes` to seconds.
+fn parse_wait(s: &str) -> Option<u64> {
+    let mut total = None;
+    let mut words = s.split_whitespace().peekable();
+
+    while let Some(word) = words.next() {
+        let (value, unit) = if let Some(value) = word.strip_suffix('s').and_then(|x| x.parse::<u64>().ok()) {
+            (value, 1)
+        } else if let Some(value) = word.strip_suffix('m').and_then(|x| x.parse::<u64>().ok()) {
+            (value, 60)
+        } else if let Some(value) = word.strip_suffix('h').and_then(|x| x.parse::<u64>().ok()) {
+            (value, 3600)
+        } else {
+            let value = match word {
+                "one" | "a" => 1,
+                x => match x.parse::<u64>() {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                },
+            };
+            let unit = match words.peek().copied() {
+                Some(x) if x.starts_with("second") => 1,
+                Some(x) if x.starts_with("minute") => 60,
+                Some(x) if x.starts_with("hour") => 3600,
+                _ => continue,
+            };
+            words.next();
+            (value, unit)
+        };
+        total = Some(total.unwrap_or(0) + value * unit);
+    }
+
+    total
+}
+
+/// What is known about the submissions of one part.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartHistory {
+    /// Answers that were rejected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<String>,
+    /// The highest answer that was too low, the answer is greater than this.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_low: Option<i128>,
+    /// The lowest answer that was too high, the answer is smaller than this.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_high: Option<i128>,
+    /// Unix timestamp (in seconds) before which no answer should be submitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown_until: Option<u64>,
+}
+
+/// Why a submission was refused locally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    KnownWrong,
+    TooLow(i128),
+    TooHigh(i128),
+    Cooldown(u64),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and rejected."),
+            Refusal::TooLow(bound) => write!(f, "this answer is too low, {bound} was already too low."),
+            Refusal::TooHigh(bound) => write!(f, "this answer is too high, {bound} was already too high."),
+            Refusal::Cooldown(secs) => write!(f, "the cooldown of the last submission has {secs}s left."),
+        }
+    }
+}
+
+impl PartHistory {
+    /// Checks whether `answer` may be submitted at `now` (unix timestamp in seconds).
+    pub fn check(&self, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(until) = self.cooldown_until.filter(|until| *until > now) {
+            return Err(Refusal::Cooldown(until - now));
+        }
+
+        if self.wrong.iter().any(|x| x == answer) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            if let Some(bound) = self.too_low.filter(|bound| value <= *bound) {
+                return Err(Refusal::TooLow(bound));
+            }
+            if let Some(bound) = self.too_high.filter(|bound| value >= *bound) {
+                return Err(Refusal::TooHigh(bound));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the response to the submission of `answer` at `now` (unix timestamp in seconds).
+    pub fn record(&mut self, answer: &str, response: Response, now: u64) {
+        let value = answer.trim().parse::<i128>().ok();
+
+        let wait_secs = match response {
+            Response::Correct | Response::Unknown => None,
+            Response::TooSoon { wait_secs } => Some(wait_secs),
+            Response::TooHigh { wait_secs } => {
+                if let Some(value) = value {
+                    self.too_high = Some(self.too_high.map_or(value, |x| x.min(value)));
+                }
+                wait_secs
+            }
+            Response::TooLow { wait_secs } => {
+                if let Some(value) = value {
+                    self.too_low = Some(self.too_low.map_or(value, |x| x.max(value)));
+                }
+                wait_secs
+            }
+            Response::Wrong { wait_secs } => wait_secs,
+        };
+
+        if matches!(response, Response::TooHigh { .. } | Response::TooLow { .. } | Response::Wrong { .. })
+            && !self.wrong.iter().any(|x| x == answer)
+        {
+            self.wrong.push(answer.into());
+        }
+
+        // a response that could not be understood says nothing about the cooldown.
+        if response != Response::Unknown {
+            self.cooldown_until = wait_secs.map(|secs| now + secs);
+        }
+    }
+}
+
+/// The submissions of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submissions {
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub part_one: PartHistory,
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub part_two: PartHistory,
+}
+
+fn is_empty(history: &PartHistory) -> bool {
+    *history == PartHistory::default()
+}
+
+impl Submissions {
+    pub fn part_mut(&mut self, part: u8) -> &mut PartHistory {
+        match part {
+            1 => &mut self.part_one,
+            _ => &mut self.part_two,
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_submissions(id: PuzzleId) -> String {
+    format!("{}/{}.toml", get_data_dir(id.year, "submissions"), id.day)
+}
+
+/// The current unix timestamp in seconds.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+pub fn load(id: PuzzleId) -> Result<Submissions, Error> {
+    let path = get_path_for_submissions(id);
+    if !Path::new(&path).exists() {
+        return Ok(Submissions::default());
+    }
+    toml::from_str(&fs::read_to_string(path)?).map_err(|e| Error::Parser(e.to_string()))
+}
+
+pub fn save(id: PuzzleId, submissions: &Submissions) -> Result<(), Error> {
+    let path = get_path_for_submissions(id);
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, toml::to_string(submissions).map_err(|e| Error::Parser(e.to_string()))?)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_wait, PartHistory, Refusal, Response};
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(Response::parse("That's the right answer! You are one gold star closer."), Response::Correct);
+        assert_eq!(
+            Response::parse("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the [about page](/2023/about), or you can ask for hints on the [subreddit](https://www.reddit.com/r/adventofcode/).  Please wait one minute before trying again. [[Return to Day 1]](/2023/day/1)"),
+            Response::TooHigh { wait_secs: Some(60) }
+        );
+        assert_eq!(
+            Response::parse("That's not the right answer.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again."),
+            Response::Wrong { wait_secs: Some(60) }
+        );
+        assert_eq!(
+            Response::parse("That's not the right answer; your answer is too low. Because you have guessed incorrectly 6 times on this puzzle, please wait 5 minutes before trying again."),
+            Response::TooLow { wait_secs: Some(300) }
+        );
+        assert_eq!(Response::parse("That's not the right answer. [Return to Day 1]"), Response::Wrong { wait_secs: None });
+        assert_eq!(
+            Response::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait."),
+            Response::TooSoon { wait_secs: 64 }
+        );
+        assert_eq!(Response::parse("You don't seem to be solving the right level.  Did you already complete it?"), Response::Unknown);
+    }
+
+    #[test]
+    fn parses_waits() {
+        assert_eq!(parse_wait("39s"), Some(39));
+        assert_eq!(parse_wait("2m 10s"), Some(130));
+        assert_eq!(parse_wait("one minute"), Some(60));
+        assert_eq!(parse_wait("10 minutes"), Some(600));
+        assert_eq!(parse_wait("a while"), None);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut history = PartHistory::default();
+        history.record("abc", Response::Wrong { wait_secs: None }, 0);
+        assert_eq!(history.check("abc", 0), Err(Refusal::KnownWrong));
+        assert_eq!(history.check("abd", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let mut history = PartHistory::default();
+        history.record("100", Response::TooLow { wait_secs: None }, 0);
+        history.record("200", Response::TooHigh { wait_secs: None }, 0);
+        history.record("50", Response::TooLow { wait_secs: None }, 0);
+
+        assert_eq!(history.too_low, Some(100));
+        assert_eq!(history.check("99", 0), Err(Refusal::TooLow(100)));
+        assert_eq!(history.check("250", 0), Err(Refusal::TooHigh(200)));
+        assert_eq!(history.check("150", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_during_cooldown() {
+        let mut history = PartHistory::default();
+        history.record("1", Response::TooSoon { wait_secs: 30 }, 1000);
+        assert_eq!(history.check("2", 1010), Err(Refusal::Cooldown(20)));
+        assert_eq!(history.check("2", 1030), Ok(()));
+        assert_eq!(history.wrong.is_empty(), true);
+    }
+
+    #[test]
+    fn keeps_cooldown_on_unknown_responses() {
+        let mut history = PartHistory::default();
+        history.record("1", Response::Wrong { wait_secs: Some(60) }, 1000);
+        history.record("2", Response::Unknown, 1010);
+        assert_eq!(history.cooldown_until, Some(1060));
+        assert_eq!(history.check("3", 1030), Err(Refusal::Cooldown(30)));
+    }
+}
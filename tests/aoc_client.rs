@@ -0,0 +1,112 @@
+#![allow(clippy::bool_assert_comparison)]
+
+mod common;
+
+use advent_of_code::day;
+use advent_of_code::template::aoc_client::{AocClient, AocClientError};
+use common::{Route, StandInServer};
+
+const PUZZLE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+treb7uchet
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54597</code>.</p>
+<form method="post" action="1/answer"></form>
+</main></body></html>"#;
+
+fn client(server: &StandInServer) -> AocClient {
+    AocClient::new(&server.url, "cookie", advent_of_code::year!(2023))
+}
+
+#[test]
+fn fetches_input_with_session_cookie() {
+    let server = StandInServer::start(vec![Route::new("GET", "/2023/day/1/input", 200, "1abc2\n")]);
+
+    assert_eq!(client(&server).input(day!(1)).unwrap(), "1abc2\n");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2023/day/1/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=cookie"));
+    assert_eq!(requests[0].header("User-Agent").is_some(), true);
+}
+
+#[test]
+fn fetches_puzzle_as_markdown() {
+    let server = StandInServer::start(vec![Route::new("GET", "/2023/day/1", 200, PUZZLE)]);
+
+    let expected = [
+        r"\--- Day 1: Trebuchet?! ---",
+        "----------",
+        "",
+        "For example:",
+        "",
+        "```",
+        "1abc2",
+        "treb7uchet",
+        "",
+        "```",
+        "",
+        "Adding these together produces `*142*`.",
+        "",
+        "Your puzzle answer was `54597`.",
+        "",
+    ]
+    .join("\n");
+
+    assert_eq!(client(&server).puzzle(day!(1)).unwrap(), expected);
+}
+
+#[test]
+fn submits_answer_as_form() {
+    let server = StandInServer::start(vec![Route::new(
+        "POST",
+        "/2023/day/8/answer",
+        200,
+        "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+    )]);
+
+    let response = client(&server).submit(day!(8), 2, "42").unwrap();
+    assert_eq!(response, "That's the right answer! You are *one gold star* closer.");
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].body, "level=2&answer=42");
+}
+
+#[test]
+fn errors_on_expired_session() {
+    let server = StandInServer::start(vec![Route::new(
+        "GET",
+        "/2023/day/1/input",
+        400,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+    )]);
+
+    assert_eq!(matches!(client(&server).input(day!(1)), Err(AocClientError::SessionExpired)), true);
+}
+
+#[test]
+fn errors_on_locked_puzzle() {
+    let server = StandInServer::start(vec![]);
+
+    assert_eq!(matches!(client(&server).puzzle(day!(25)), Err(AocClientError::PuzzleNotUnlocked)), true);
+}
+
+#[test]
+fn errors_on_rate_limit() {
+    let server = StandInServer::start(vec![Route::new("GET", "/2023/day/1/input", 429, "slow down").with_header("Retry-After", "30")]);
+
+    assert_eq!(matches!(client(&server).input(day!(1)), Err(AocClientError::RateLimited { retry_after: Some(30) })), true);
+}
+
+#[test]
+fn errors_on_unreachable_server() {
+    let client = AocClient::new("http://127.0.0.1:1", "cookie", advent_of_code::year!(2023));
+
+    assert_eq!(matches!(client.input(day!(1)), Err(AocClientError::Transport(_))), true);
+}
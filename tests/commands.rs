@@ -0,0 +1,276 @@
+#![allow(clippy::bool_assert_comparison)]
+
+mod common;
+
+use std::{fs, path::Path, process::Command};
+
+use common::{run_against, work_dir, MockAocServer, SubmitResponse};
+
+const MAIN_BIN: &str = env!("CARGO_BIN_EXE_advent_of_code");
+const DAY_01_BIN: &str = env!("CARGO_BIN_EXE_2023_01");
+
+const ARTICLE: &str = "<h2>--- Day 1: Trebuchet?! ---</h2>\n<p>For example:</p>\n<pre><code>1abc2\n</code></pre>\n<p>This produces <code><em>12</em></code>.</p>";
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn download_writes_input_and_puzzle() {
+    let server = MockAocServer::new(2023).puzzle(1, ARTICLE).input(1, "1abc2\ntreb7uchet\n").start();
+    let dir = work_dir("download");
+
+    let output = run_against(MAIN_BIN, &["download", "1"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), true);
+    assert_eq!(fs::read_to_string(dir.path().join("data/2023/inputs/01.txt")).unwrap(), "1abc2\ntreb7uchet\n");
+    let puzzle = fs::read_to_string(dir.path().join("data/2023/puzzles/01.md")).unwrap();
+    assert_eq!(puzzle.starts_with("\\--- Day 1: Trebuchet?! ---"), true);
+    assert_eq!(puzzle.contains("```\n1abc2\n\n```"), true);
+}
+
+#[test]
+fn download_uses_year_argument() {
+    let server = MockAocServer::new(2022).puzzle(1, ARTICLE).input(1, "1abc2\n").start();
+    let dir = work_dir("download-year");
+
+    let output = run_against(MAIN_BIN, &["download", "1", "--year", "2022"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), true);
+    assert_eq!(server.requests()[0].path, "/2022/day/1/input");
+    assert_eq!(dir.path().join("data/2022/inputs/01.txt").exists(), true);
+    assert_eq!(dir.path().join("data/2023").exists(), false);
+}
+
+#[test]
+fn download_fails_on_locked_puzzle() {
+    let server = MockAocServer::new(2023).start();
+    let dir = work_dir("download-locked");
+
+    let output = run_against(MAIN_BIN, &["download", "2"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), false);
+    assert_eq!(String::from_utf8_lossy(&output.stderr).contains("not unlocked"), true);
+    assert_eq!(dir.path().join("data/2023/inputs/02.txt").exists(), false);
+}
+
+#[test]
+fn read_prints_puzzle() {
+    let server = MockAocServer::new(2023).puzzle(1, ARTICLE).start();
+    let dir = work_dir("read");
+
+    let output = run_against(MAIN_BIN, &["read", "1"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), true);
+    assert_eq!(stdout(&output).contains("For example:"), true);
+    assert_eq!(dir.path().join("data/2023/puzzles/01.md").exists(), true);
+}
+
+fn day_01_dir(name: &str) -> tempfile::TempDir {
+    let dir = work_dir(name);
+    fs::create_dir_all(dir.path().join("data/2023/inputs")).unwrap();
+    fs::write(dir.path().join("data/2023/inputs/01.txt"), "1abc2\n").unwrap();
+    dir
+}
+
+#[test]
+fn submit_records_right_answer() {
+    let server = MockAocServer::new(2023).submit_response(1, SubmitResponse::Right).start();
+    let dir = day_01_dir("submit-right");
+
+    let output = run_against(DAY_01_BIN, &["--submit", "1"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), true);
+    assert_eq!(stdout(&output).contains("That's the right answer!"), true);
+    assert_eq!(server.requests()[0].body, "level=1&answer=12");
+    assert_eq!(fs::read_to_string(dir.path().join("data/2023/answers/01.toml")).unwrap(), "part_one = \"12\"\n");
+}
+
+#[test]
+fn submit_refuses_known_wrong_answer() {
+    let server = MockAocServer::new(2023).submit_response(1, SubmitResponse::Wrong).start();
+    let dir = day_01_dir("submit-wrong");
+    fs::create_dir_all(dir.path().join("data/2023/submissions")).unwrap();
+
+    run_against(DAY_01_BIN, &["--submit", "1"], dir.path(), &server);
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(dir.path().join("data/2023/answers/01.toml").exists(), false);
+
+    // clear the cooldown of the first submission, the answer itself stays known as wrong.
+    let path = dir.path().join("data/2023/submissions/01.toml");
+    let history = fs::read_to_string(&path).unwrap();
+    assert_eq!(history.contains("cooldown_until"), true);
+    let history: String = history.lines().filter(|l| !l.starts_with("cooldown_until")).map(|l| format!("{l}\n")).collect();
+    fs::write(&path, history).unwrap();
+
+    let output = run_against(DAY_01_BIN, &["--submit", "1"], dir.path(), &server);
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(String::from_utf8_lossy(&output.stderr).contains("already submitted and rejected"), true);
+}
+
+#[test]
+fn submit_remembers_cooldown() {
+    let server = MockAocServer::new(2023).submit_response(1, SubmitResponse::TooSoon).submit_response(1, SubmitResponse::Right).start();
+    let dir = day_01_dir("submit-too-soon");
+
+    run_against(DAY_01_BIN, &["--submit", "1"], dir.path(), &server);
+    let output = run_against(DAY_01_BIN, &["--submit", "1"], dir.path(), &server);
+
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(String::from_utf8_lossy(&output.stderr).contains("cooldown"), true);
+    assert_eq!(dir.path().join("data/2023/answers/01.toml").exists(), false);
+}
+
+#[test]
+fn submit_refuses_other_inputs() {
+    let server = MockAocServer::new(2023).submit_response(1, SubmitResponse::Right).start();
+    let dir = day_01_dir("submit-other-input");
+    fs::write(dir.path().join("stress.txt"), "9\n").unwrap();
+
+    let output = run_against(DAY_01_BIN, &["--submit", "1", "--input", "stress.txt"], dir.path(), &server);
+
+    assert_eq!(server.requests().is_empty(), true);
+    assert_eq!(String::from_utf8_lossy(&output.stderr).contains("not computed on the puzzle input"), true);
+}
+
+#[test]
+fn solution_reads_selected_input() {
+    let server = MockAocServer::new(2023).start();
+    let dir = day_01_dir("input-source");
+    fs::write(dir.path().join("stress.txt"), "9\n").unwrap();
+    fs::create_dir_all(dir.path().join("data/2023/examples")).unwrap();
+    fs::write(dir.path().join("data/2023/examples/01-2.txt"), "4nine\n").unwrap();
+
+    assert_eq!(stdout(&run_against(DAY_01_BIN, &[], dir.path(), &server)).contains("Part 1: \u{1b}[1m12"), true);
+    assert_eq!(stdout(&run_against(DAY_01_BIN, &["--input", "stress.txt"], dir.path(), &server)).contains("Part 1: \u{1b}[1m99"), true);
+    assert_eq!(stdout(&run_against(DAY_01_BIN, &["--example", "2"], dir.path(), &server)).contains("Part 2: \u{1b}[1m49"), true);
+}
+
+#[test]
+fn submit_responses_are_served_in_order() {
+    let server = MockAocServer::new(2023).submit_response(1, SubmitResponse::TooHigh).submit_response(1, SubmitResponse::Right).start();
+    let client = advent_of_code::template::aoc_client::AocClient::new(&server.url, "cookie", advent_of_code::year!(2023));
+    let day = advent_of_code::day!(1);
+
+    assert_eq!(client.submit(day, 1, "100").unwrap().contains("too high"), true);
+    assert_eq!(client.submit(day, 1, "50").unwrap().contains("right answer"), true);
+    assert_eq!(client.submit(day, 1, "50").unwrap().contains("right answer"), true);
+}
+
+#[test]
+fn examples_are_extracted_from_puzzle() {
+    let server = MockAocServer::new(2023).puzzle(1, ARTICLE).start();
+    let dir = work_dir("examples");
+    run_against(MAIN_BIN, &["read", "1"], dir.path(), &server);
+
+    let output = run_against(MAIN_BIN, &["examples", "1"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), true);
+    assert_eq!(fs::read_to_string(dir.path().join("data/2023/examples/01-1.txt")).unwrap(), "1abc2\n");
+    assert_eq!(fs::read_to_string(dir.path().join("data/2023/examples/01-1.toml")).unwrap(), "part_one = \"12\"\n");
+}
+
+#[test]
+fn download_selected_days() {
+    let server = MockAocServer::new(2023).puzzle(3, ARTICLE).input(3, "3\n").puzzle(5, ARTICLE).input(5, "5\n").start();
+    let dir = work_dir("download-selection");
+
+    let output = run_against(MAIN_BIN, &["download", "3,5"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), true);
+    assert_eq!(fs::read_to_string(dir.path().join("data/2023/inputs/03.txt")).unwrap(), "3\n");
+    assert_eq!(fs::read_to_string(dir.path().join("data/2023/inputs/05.txt")).unwrap(), "5\n");
+    assert_eq!(dir.path().join("data/2023/inputs/04.txt").exists(), false);
+}
+
+#[test]
+fn download_continues_after_failed_day() {
+    let server = MockAocServer::new(2023).puzzle(1, ARTICLE).input(1, "1\n").puzzle(3, ARTICLE).input(3, "3\n").start();
+    let dir = work_dir("download-continue");
+
+    let output = run_against(MAIN_BIN, &["download", "1..=3"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), false);
+    assert_eq!(String::from_utf8_lossy(&output.stderr).contains("Could not download day(s) 02."), true);
+    assert_eq!(dir.path().join("data/2023/inputs/01.txt").exists(), true);
+    assert_eq!(dir.path().join("data/2023/inputs/03.txt").exists(), true);
+}
+
+#[test]
+fn scaffold_skips_existing_days() {
+    let server = MockAocServer::new(2023).start();
+    let dir = work_dir("scaffold-existing");
+    fs::create_dir_all(dir.path().join("src/bin")).unwrap();
+    fs::write(dir.path().join("src/bin/2023_01.rs"), "// solved").unwrap();
+
+    let output = run_against(MAIN_BIN, &["scaffold", "1,2"], dir.path(), &server);
+
+    assert_eq!(output.status.success(), true);
+    assert_eq!(stdout(&output).contains("Skipped day 01"), true);
+    assert_eq!(fs::read_to_string(dir.path().join("src/bin/2023_01.rs")).unwrap(), "// solved");
+    assert_eq!(dir.path().join("src/bin/2023_02.rs").exists(), true);
+}
+
+/// Copies the crate without its solutions into a work dir, to build days of the test's own.
+fn crate_dir(name: &str) -> tempfile::TempDir {
+    let dir = work_dir(name);
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for file in ["Cargo.toml", "Cargo.lock", "build.rs", "src/lib.rs", "src/day.rs", "src/main.rs", "src/runner.rs"] {
+        if manifest_dir.join(file).exists() {
+            fs::create_dir_all(dir.path().join(file).parent().unwrap()).unwrap();
+            fs::copy(manifest_dir.join(file), dir.path().join(file)).unwrap();
+        }
+    }
+    copy_dir(&manifest_dir.join("src/template"), &dir.path().join("src/template"));
+    fs::create_dir_all(dir.path().join("src/bin")).unwrap();
+    fs::create_dir_all(dir.path().join("data/2023/inputs")).unwrap();
+    dir
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            copy_dir(&path, &to.join(path.file_name().unwrap()));
+        } else {
+            fs::copy(&path, to.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+}
+
+#[test]
+fn all_reports_days_that_do_not_compile() {
+    let server = MockAocServer::new(2023).start();
+    let dir = crate_dir("all-broken-day");
+    let solution = "advent_of_code::solution!(DAY_NUMBER);\n\npub fn part_one(input: &str) -> Option<usize> {\n  Some(input.len())\n}\n\npub fn part_two(_input: &str) -> Option<u32> {\n  None\n}\n";
+    fs::write(dir.path().join("src/bin/2023_01.rs"), solution.replace("DAY_NUMBER", "1")).unwrap();
+    fs::write(dir.path().join("src/bin/2023_02.rs"), solution.replace("DAY_NUMBER", "2") + "\nfn broken() -> u32 {\n  \"not a number\"\n}\n").unwrap();
+    fs::write(dir.path().join("data/2023/inputs/01.txt"), "12345").unwrap();
+    fs::write(dir.path().join("data/2023/inputs/02.txt"), "12345").unwrap();
+
+    // the dependencies are built once next to the crate's own target dir, the copy itself is rebuilt by every run.
+    let target_dir = Path::new(MAIN_BIN).ancestors().nth(2).unwrap().join("broken-day");
+    let output = Command::new(MAIN_BIN)
+        .args(["all", "1,2", "--report", "json", "--out", "report.json"])
+        .current_dir(dir.path())
+        .env("CARGO_TARGET_DIR", target_dir)
+        .env("CARGO_NET_OFFLINE", "true")
+        .env("AOC_BASE_URL", &server.url)
+        .env("AOC_YEAR", "2023")
+        .output()
+        .unwrap();
+
+    assert_eq!(stdout(&output).contains("could not start the runner"), true);
+
+    let report: Vec<serde_json::Value> = serde_json::from_str(&fs::read_to_string(dir.path().join("report.json")).unwrap()).unwrap();
+    let entry = |day: u64, part: u64| report.iter().find(|e| e["day"] == day && e["part"] == part).unwrap();
+    assert_eq!(entry(1, 1)["status"], "unverified");
+    assert_eq!(entry(1, 1)["answer"], "5");
+    assert_eq!(entry(1, 2)["status"], "unsolved");
+    for part in [1, 2] {
+        assert_eq!(entry(2, part)["status"], "errored");
+        assert_eq!(entry(2, part)["error"].as_str().unwrap().contains("mismatched types"), true);
+    }
+    assert_eq!(String::from_utf8_lossy(&output.stderr).contains("mismatched types"), true);
+}
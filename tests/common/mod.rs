@@ -0,0 +1,220 @@
+//! A local stand-in for the Advent of Code website, serving canned responses over HTTP.
+//! [`StandInServer`] serves arbitrary routes, [`MockAocServer`] configures it with puzzles, inputs
+//! and submit responses the way the real website would serve them.
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+    process::{Command, Output},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use tempfile::TempDir;
+
+/// A request received by the server.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+}
+
+/// A canned response, served for requests matching `method` and `path`.
+/// Routes sharing a method and path are served in order, the last one is repeated.
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub method: &'static str,
+    pub path: String,
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl Route {
+    pub fn new(method: &'static str, path: &str, status: u16, body: &str) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+pub struct StandInServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StandInServer {
+    /// Starts a server on a free local port, unknown routes are answered with a 404.
+    pub fn start(routes: Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            let mut hits: HashMap<(String, String), usize> = HashMap::new();
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                if let Some(request) = handle(stream, &routes, &mut hits) {
+                    received.lock().unwrap().push(request);
+                }
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// The requests received so far.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn handle(mut stream: TcpStream, routes: &[Route], hits: &mut HashMap<(String, String), usize>) -> Option<Request> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next()?.to_string(), parts.next()?.to_string());
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let length = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    let matching: Vec<&Route> = routes.iter().filter(|r| r.method == method && r.path == path).collect();
+    let hit = hits.entry((method.clone(), path.clone())).or_default();
+    let route = matching.get((*hit).min(matching.len().saturating_sub(1))).copied();
+    *hit += 1;
+    let (status, extra_headers, response_body) = match route {
+        Some(route) => (route.status, route.headers.clone(), route.body.clone()),
+        None => (404, vec![], "404 Not Found".to_string()),
+    };
+
+    let mut response = format!("HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\nConnection: close\r\n", response_body.len());
+    for (name, value) in extra_headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str("\r\n");
+    response.push_str(&response_body);
+    stream.write_all(response.as_bytes()).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// A canned response to a submission.
+#[derive(Debug, Clone, Copy)]
+pub enum SubmitResponse {
+    Right,
+    Wrong,
+    TooHigh,
+    TooSoon,
+}
+
+impl SubmitResponse {
+    fn html(self) -> &'static str {
+        match self {
+            SubmitResponse::Right => "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>",
+            SubmitResponse::Wrong => "<main><article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article></main>",
+            SubmitResponse::TooHigh => "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article></main>",
+            SubmitResponse::TooSoon => "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 42s left to wait.</p></article></main>",
+        }
+    }
+}
+
+/// A mock of the Advent of Code website for one year, configured per test.
+pub struct MockAocServer {
+    year: u16,
+    routes: Vec<Route>,
+}
+
+impl MockAocServer {
+    pub fn new(year: u16) -> Self {
+        Self { year, routes: vec![] }
+    }
+
+    /// Serves the description page of a day, `article` is the inner HTML of its `<article>`.
+    pub fn puzzle(mut self, day: u8, article: &str) -> Self {
+        let html = format!("<html><body><main><article class=\"day-desc\">{article}</article></main></body></html>");
+        self.routes.push(Route::new("GET", &format!("/{}/day/{day}", self.year), 200, &html));
+        self
+    }
+
+    pub fn input(mut self, day: u8, input: &str) -> Self {
+        self.routes.push(Route::new("GET", &format!("/{}/day/{day}/input", self.year), 200, input));
+        self
+    }
+
+    /// Adds a response to the submissions of a day, responses are served in order.
+    pub fn submit_response(mut self, day: u8, response: SubmitResponse) -> Self {
+        self.routes.push(Route::new("POST", &format!("/{}/day/{day}/answer", self.year), 200, response.html()));
+        self
+    }
+
+    pub fn start(self) -> StandInServer {
+        StandInServer::start(self.routes)
+    }
+}
+
+/// A fresh working directory for a test, with an empty `data` folder. It is removed when dropped.
+pub fn work_dir(name: &str) -> TempDir {
+    let dir = tempfile::Builder::new().prefix(&format!("aoc-test-{name}-")).tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("data")).unwrap();
+    dir
+}
+
+/// Runs a binary of the crate in `dir`, pointed at `server`.
+pub fn run_against(bin: &str, args: &[&str], dir: &Path, server: &StandInServer) -> Output {
+    Command::new(bin)
+        .args(args)
+        .current_dir(dir)
+        .env("AOC_BASE_URL", &server.url)
+        .env("AOC_SESSION", "cookie")
+        .env("AOC_YEAR", "2023")
+        .output()
+        .unwrap()
+}